@@ -0,0 +1,217 @@
+//! Adapters wrapping a [`Consumer`] or [`Producer`] to transform the values going through the
+//! queue, so that code on either side can work with its own types.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::SingleSlotQueue;
+//! let mut queue = SingleSlotQueue::<u8>::new();
+//! let (cons, mut prod) = queue.split();
+//! let mut cons = cons.map_values(|code| code == 1);
+//!
+//! prod.enqueue(1);
+//! assert!(cons.dequeue() == Some(true));
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    Consumer, Overwrite, Producer,
+};
+
+impl<'a, T, S: Storage<T>> Consumer<'a, T, S> {
+    /// Wrap this consumer to convert every value with `f` as it is read.
+    ///
+    /// This is not named `map`, so that it does not shadow `StreamExt::map` under the `async`
+    /// feature.
+    #[inline]
+    pub fn map_values<U, F: FnMut(T) -> U>(self, f: F) -> Map<'a, T, F, S> {
+        Map { cons: self, f }
+    }
+
+    /// Wrap this consumer to discard every value for which `pred` returns `false` as it is read.
+    #[inline]
+    pub fn filter_values<P: FnMut(&T) -> bool>(self, pred: P) -> Filter<'a, T, P, S> {
+        Filter { cons: self, pred }
+    }
+
+    /// Wrap this consumer to call `f` on every value it reads, e.g. to log it or toggle a GPIO,
+    /// without changing the value.
+    #[inline]
+    pub fn inspect_values<F: FnMut(&T)>(self, f: F) -> Inspect<'a, T, F, S> {
+        Inspect { cons: self, f }
+    }
+}
+
+/// Consumer converting every value it reads, returned by [`Consumer::map_values`].
+pub struct Map<'a, T, F, S: Storage<T> = Inline<T>> {
+    cons: Consumer<'a, T, S>,
+    f: F,
+}
+
+impl<'a, T, U, F: FnMut(T) -> U, S: Storage<T>> Map<'a, T, F, S> {
+    /// Try reading a value from the queue and converting it, as with [`Consumer::dequeue`].
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<U> {
+        self.cons.dequeue().map(&mut self.f)
+    }
+
+    /// Try reading a value without dequeuing, and convert a copy of it, as with
+    /// [`Consumer::peek`].
+    #[inline]
+    pub fn peek(&mut self) -> Option<U>
+    where
+        T: Copy,
+    {
+        self.cons.peek().map(&mut self.f)
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// Check if the corresponding [`Producer`](crate::Producer) has been dropped.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.cons.is_closed()
+    }
+
+    /// Unwrap the underlying consumer.
+    #[inline]
+    pub fn into_inner(self) -> Consumer<'a, T, S> {
+        self.cons
+    }
+}
+
+/// Consumer discarding the values that do not match a predicate, returned by
+/// [`Consumer::filter_values`].
+pub struct Filter<'a, T, P, S: Storage<T> = Inline<T>> {
+    cons: Consumer<'a, T, S>,
+    pred: P,
+}
+
+impl<'a, T, P: FnMut(&T) -> bool, S: Storage<T>> Filter<'a, T, P, S> {
+    /// Try reading a value matching the predicate from the queue.
+    ///
+    /// Values that do not match are dequeued and dropped, until a matching value is read or the
+    /// queue is observed empty. Like [`Consumer::dequeue`], this never blocks.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        loop {
+            let v = self.cons.dequeue()?;
+            if (self.pred)(&v) {
+                return Some(v);
+            }
+        }
+    }
+
+    /// Check if there is a value in the queue, whether or not it matches the predicate.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// Check if the corresponding [`Producer`](crate::Producer) has been dropped.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.cons.is_closed()
+    }
+
+    /// Unwrap the underlying consumer.
+    #[inline]
+    pub fn into_inner(self) -> Consumer<'a, T, S> {
+        self.cons
+    }
+}
+
+/// Consumer calling a closure on every value it reads, returned by [`Consumer::inspect_values`].
+pub struct Inspect<'a, T, F, S: Storage<T> = Inline<T>> {
+    cons: Consumer<'a, T, S>,
+    f: F,
+}
+
+impl<'a, T, F: FnMut(&T), S: Storage<T>> Inspect<'a, T, F, S> {
+    /// Try reading a value from the queue, as with [`Consumer::dequeue`], and call the closure on
+    /// it.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        let v = self.cons.dequeue()?;
+        (self.f)(&v);
+        Some(v)
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// Check if the corresponding [`Producer`](crate::Producer) has been dropped.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.cons.is_closed()
+    }
+
+    /// Unwrap the underlying consumer.
+    #[inline]
+    pub fn into_inner(self) -> Consumer<'a, T, S> {
+        self.cons
+    }
+}
+
+impl<'a, T, S: Storage<T>> Producer<'a, T, S> {
+    /// Wrap this producer to convert every value with `f` before it is written, e.g. to accept
+    /// an `&Event` and store a compact code.
+    #[inline]
+    pub fn contramap<F>(self, f: F) -> Contramap<'a, T, F, S> {
+        Contramap { prod: self, f }
+    }
+}
+
+/// Producer converting every value before it is written, returned by [`Producer::contramap`].
+pub struct Contramap<'a, T, F, S: Storage<T> = Inline<T>> {
+    prod: Producer<'a, T, S>,
+    f: F,
+}
+
+impl<'a, T, F, S: Storage<T>> Contramap<'a, T, F, S> {
+    /// Convert a value and write it into the queue, as with [`Producer::enqueue`].
+    ///
+    /// If the value could not be written, it is returned converted.
+    #[inline]
+    pub fn enqueue<U>(&mut self, val: U) -> Option<T>
+    where
+        F: FnMut(U) -> T,
+    {
+        self.prod.enqueue((self.f)(val))
+    }
+
+    /// Convert a value and write it into the queue, overwriting the old value if it exists, as
+    /// with [`Producer::enqueue_overwrite`].
+    #[inline]
+    pub fn enqueue_overwrite<U>(&mut self, val: U) -> Overwrite<T>
+    where
+        F: FnMut(U) -> T,
+    {
+        self.prod.enqueue_overwrite((self.f)(val))
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.prod.is_empty()
+    }
+
+    /// Check if the corresponding [`Consumer`] is still around to read enqueued values.
+    #[inline]
+    pub fn is_consumer_connected(&self) -> bool {
+        self.prod.is_consumer_connected()
+    }
+
+    /// Unwrap the underlying producer.
+    #[inline]
+    pub fn into_inner(self) -> Producer<'a, T, S> {
+        self.prod
+    }
+}
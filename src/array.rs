@@ -0,0 +1,132 @@
+//! Fixed number of independent single slots, shared by one producer and one consumer.
+//!
+//! Useful for protocols with a fixed set of message channels, e.g. one slot per endpoint.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{array::SlotArray, Overwrite};
+//! let mut slots = SlotArray::<u32, 4>::new();
+//! let (mut cons, mut prod) = slots.split();
+//!
+//! assert!(prod.enqueue(2, 20) == None);
+//! assert!(prod.enqueue(2, 21) == Some(21));
+//! assert!(prod.enqueue(0, 0) == None);
+//!
+//! assert!(cons.dequeue(1) == None);
+//! assert!(cons.dequeue(2) == Some(20));
+//!
+//! // Slots can also be polled in index order.
+//! assert!(cons.dequeue_any() == Some((0, 0)));
+//! assert!(cons.dequeue_any() == None);
+//!
+//! // Or overwrite the unread value in a slot, getting it back.
+//! assert!(prod.enqueue_overwrite(3, 30) == Overwrite::Enqueued);
+//! assert!(prod.enqueue_overwrite(3, 31) == Overwrite::Overwrote(30));
+//! ```
+
+use crate::{slot::RawSlot, Overwrite};
+
+/// `N` independent single slots.
+pub struct SlotArray<T, const N: usize> {
+    slots: [RawSlot<T>; N],
+}
+
+impl<T, const N: usize> SlotArray<T, N> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        SlotArray {
+            slots: [const { RawSlot::new() }; N],
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T, N>, Producer<'_, T, N>) {
+        (Consumer { array: self }, Producer { array: self })
+    }
+
+    /// The number of slots.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Check if there are no slots at all, i.e. `N` is 0.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+/// Read handle to a slot array.
+///
+/// All methods taking an index panic if it is out of bounds.
+pub struct Consumer<'a, T, const N: usize> {
+    array: &'a SlotArray<T, N>,
+}
+
+impl<'a, T, const N: usize> Consumer<'a, T, N> {
+    /// Try reading a value from slot `idx`.
+    ///
+    /// This method never blocks. If the [`Producer`] is in the middle of writing a value, that
+    /// value is not published yet and `None` is returned.
+    #[inline]
+    pub fn dequeue(&mut self, idx: usize) -> Option<T> {
+        self.array.slots[idx].take()
+    }
+
+    /// Try reading a value from the first slot holding one, along with its index.
+    #[inline]
+    pub fn dequeue_any(&mut self) -> Option<(usize, T)> {
+        self.array
+            .slots
+            .iter()
+            .enumerate()
+            .find_map(|(idx, slot)| slot.take().map(|v| (idx, v)))
+    }
+
+    /// Check if there is a value in slot `idx`.
+    #[inline]
+    pub fn is_empty(&self, idx: usize) -> bool {
+        !self.array.slots[idx].is_full()
+    }
+}
+
+impl<'a, T: Copy, const N: usize> Consumer<'a, T, N> {
+    /// Try reading a value from slot `idx` without dequeuing.
+    #[inline]
+    pub fn peek(&mut self, idx: usize) -> Option<T> {
+        self.array.slots[idx].peek()
+    }
+}
+
+/// Write handle to a slot array.
+///
+/// All methods taking an index panic if it is out of bounds.
+pub struct Producer<'a, T, const N: usize> {
+    array: &'a SlotArray<T, N>,
+}
+
+impl<'a, T, const N: usize> Producer<'a, T, N> {
+    /// Write a value into slot `idx`. If there is a value already in that slot, this will
+    /// return the value given to this method.
+    #[inline]
+    pub fn enqueue(&mut self, idx: usize, val: T) -> Option<T> {
+        self.array.slots[idx].put(val)
+    }
+
+    /// Write a value into slot `idx`, overwriting the old value if it exists.
+    ///
+    /// Returns the old value in [`Overwrite::Overwrote`] if it was overwritten, or the value given
+    /// to this method in [`Overwrite::Rejected`] if the [`Consumer`] is in the middle of reading
+    /// that slot.
+    #[inline]
+    pub fn enqueue_overwrite(&mut self, idx: usize, val: T) -> Overwrite<T> {
+        self.array.slots[idx].replace(val)
+    }
+
+    /// Check if there is a value in slot `idx`.
+    #[inline]
+    pub fn is_empty(&self, idx: usize) -> bool {
+        !self.array.slots[idx].is_full()
+    }
+}
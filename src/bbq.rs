@@ -0,0 +1,473 @@
+//! Byte buffer with contiguous write and read grants, in the style of [bbqueue].
+//!
+//! The producer asks for a contiguous region of free bytes, fills it in place (e.g. with a DMA
+//! transfer), and commits however many bytes it actually wrote. The consumer then gets the
+//! committed bytes as a contiguous region, and releases however many it has processed. Regions
+//! never wrap around the end of the buffer; instead, the producer skips the free bytes left at the
+//! end and starts again from the front.
+//!
+//! [bbqueue]: https://docs.rs/bbqueue
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::bbq::GrantBuffer;
+//! let mut buf = GrantBuffer::<8>::new();
+//! let (mut cons, mut prod) = buf.split();
+//!
+//! let mut grant = prod.grant_exact(4).unwrap();
+//! grant.copy_from_slice(b"ping");
+//! grant.commit(4);
+//!
+//! let grant = cons.read().unwrap();
+//! assert!(&grant[..] == b"ping");
+//! grant.release(2);
+//!
+//! assert!(&cons.read().unwrap()[..] == b"ng");
+//! ```
+
+use atomic_polyfill::{AtomicUsize, Ordering};
+#[cfg(feature = "embedded-io")]
+use core::hint;
+#[cfg(feature = "embedded-io-async")]
+use core::task::Poll;
+use core::{
+    cell::UnsafeCell,
+    cmp::min,
+    fmt,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+#[cfg(feature = "async")]
+use crate::waker::WakerCell;
+
+/// Byte buffer of `N` bytes, with one [`Producer`] and one [`Consumer`].
+pub struct GrantBuffer<const N: usize> {
+    buf: UnsafeCell<[u8; N]>,
+    /// Start of the committed bytes. Only written by the consumer.
+    read: AtomicUsize,
+    /// End of the committed bytes. Only written by the producer.
+    write: AtomicUsize,
+    /// End of the readable bytes when the committed bytes wrap around to the front, i.e. when
+    /// `write < read`. Only written by the producer.
+    last: AtomicUsize,
+    /// Woken when bytes are committed.
+    #[cfg(feature = "async")]
+    rx_waker: WakerCell,
+    /// Woken when bytes are released.
+    #[cfg(feature = "async")]
+    tx_waker: WakerCell,
+}
+
+impl<const N: usize> GrantBuffer<N> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        GrantBuffer {
+            buf: UnsafeCell::new([0; N]),
+            read: AtomicUsize::new(0),
+            write: AtomicUsize::new(0),
+            last: AtomicUsize::new(0),
+            #[cfg(feature = "async")]
+            rx_waker: WakerCell::new(),
+            #[cfg(feature = "async")]
+            tx_waker: WakerCell::new(),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, N>, Producer<'_, N>) {
+        (Consumer { bbq: self }, Producer { bbq: self })
+    }
+
+    /// The number of bytes the buffer can hold.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Get `len` bytes from `start`.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must be owned by the caller's handle, and `start + len <= N`.
+    #[inline]
+    unsafe fn region(&self, start: usize, len: usize) -> *mut [u8] {
+        let ptr = unsafe { self.buf.get().cast::<u8>().add(start) };
+        ptr::slice_from_raw_parts_mut(ptr, len)
+    }
+}
+
+/// Read handle to a grant buffer.
+pub struct Consumer<'a, const N: usize> {
+    bbq: &'a GrantBuffer<N>,
+}
+
+impl<'a, const N: usize> Consumer<'a, N> {
+    /// Get the oldest contiguous region of committed bytes, if any.
+    ///
+    /// If the committed bytes wrap around the end of the buffer, only the bytes up to the end are
+    /// returned; the rest is returned once they are released.
+    pub fn read(&mut self) -> Option<ReadGrant<'_, 'a, N>> {
+        let bbq = self.bbq;
+        let write = bbq.write.load(Ordering::Acquire);
+        let last = bbq.last.load(Ordering::Acquire);
+        let mut read = bbq.read.load(Ordering::Relaxed);
+
+        // Everything up to the end has been read, move on to the bytes at the front.
+        if read == last && write < read {
+            read = 0;
+            bbq.read.store(0, Ordering::Release);
+        }
+
+        let end = if write < read { last } else { write };
+        if end == read {
+            return None;
+        }
+
+        Some(ReadGrant {
+            cons: self,
+            start: read,
+            len: end - read,
+        })
+    }
+
+    /// Check if there are no committed bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bbq.read.load(Ordering::Relaxed) == self.bbq.write.load(Ordering::Relaxed)
+    }
+
+    /// Copy committed bytes into `buf`, release them, and return how many were copied.
+    ///
+    /// Bytes are read across the end of the buffer if needed. Returns 0 if there are no committed
+    /// bytes, or `buf` is empty.
+    pub fn read_bytes(&mut self, buf: &mut [u8]) -> usize {
+        let mut copied = 0;
+        while copied < buf.len() {
+            let Some(grant) = self.read() else {
+                break;
+            };
+            let len = min(grant.len(), buf.len() - copied);
+            buf[copied..copied + len].copy_from_slice(&grant[..len]);
+            grant.release(len);
+            copied += len;
+        }
+        copied
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a, const N: usize> embedded_io::ErrorType for Consumer<'a, N> {
+    type Error = core::convert::Infallible;
+}
+
+/// Busy-waits for committed bytes, and copies as many as fit into `buf`.
+#[cfg(feature = "embedded-io")]
+impl<'a, const N: usize> embedded_io::Read for Consumer<'a, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        loop {
+            match self.read_bytes(buf) {
+                0 if !buf.is_empty() => hint::spin_loop(),
+                n => return Ok(n),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a, const N: usize> embedded_io::ReadReady for Consumer<'a, N> {
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_empty())
+    }
+}
+
+/// Waits for committed bytes, and copies as many as fit into `buf`.
+#[cfg(feature = "embedded-io-async")]
+impl<'a, const N: usize> embedded_io_async::Read for Consumer<'a, N> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        core::future::poll_fn(|cx| {
+            let n = self.read_bytes(buf);
+            if n > 0 || buf.is_empty() {
+                return Poll::Ready(Ok(n));
+            }
+
+            self.bbq.rx_waker.register(cx.waker());
+
+            // Check again, in case bytes were committed before the waker was registered.
+            match self.read_bytes(buf) {
+                0 => Poll::Pending,
+                n => Poll::Ready(Ok(n)),
+            }
+        })
+        .await
+    }
+}
+
+/// Safety: Each region is only ever accessed by the handle that owns it, as gated by the
+/// `read`/`write` positions.
+unsafe impl<'a, const N: usize> Send for Consumer<'a, N> {}
+
+/// Contiguous region of committed bytes, returned by [`Consumer::read`].
+///
+/// Dropping the grant without [`release`](ReadGrant::release)ing it leaves the bytes in the
+/// buffer.
+pub struct ReadGrant<'b, 'a, const N: usize> {
+    cons: &'b mut Consumer<'a, N>,
+    start: usize,
+    len: usize,
+}
+
+impl<'b, 'a, const N: usize> ReadGrant<'b, 'a, N> {
+    /// Hand the first `used` bytes of the region back to the producer. `used` is clamped to the
+    /// length of the region.
+    #[inline]
+    pub fn release(self, used: usize) {
+        let used = min(used, self.len);
+        self.cons
+            .bbq
+            .read
+            .store(self.start + used, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.cons.bbq.tx_waker.wake();
+    }
+}
+
+impl<'b, 'a, const N: usize> Deref for ReadGrant<'b, 'a, N> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        // SAFETY: The region is owned by the consumer until it is released.
+        unsafe { &*self.cons.bbq.region(self.start, self.len) }
+    }
+}
+
+/// Safety: The region stays where it is in the buffer when the grant is moved, and is owned by the
+/// consumer until it is released.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<'b, 'a, const N: usize> embedded_dma::ReadBuffer for ReadGrant<'b, 'a, N> {
+    type Word = u8;
+
+    #[inline]
+    unsafe fn read_buffer(&self) -> (*const u8, usize) {
+        (self.cons.bbq.region(self.start, self.len).cast(), self.len)
+    }
+}
+
+/// Write handle to a grant buffer.
+pub struct Producer<'a, const N: usize> {
+    bbq: &'a GrantBuffer<N>,
+}
+
+impl<'a, const N: usize> Producer<'a, N> {
+    /// Get a contiguous region of exactly `len` free bytes, if there is one.
+    pub fn grant_exact(&mut self, len: usize) -> Option<WriteGrant<'_, 'a, N>> {
+        let write = self.bbq.write.load(Ordering::Relaxed);
+        let read = self.bbq.read.load(Ordering::Acquire);
+
+        let start = if write < read {
+            // Already wrapped around; `write` must never catch up to `read`, or the buffer would
+            // look empty.
+            if write + len < read {
+                write
+            } else {
+                return None;
+            }
+        } else if write + len <= N {
+            write
+        } else if len < read {
+            // Skip the free bytes at the end, and wrap around to the front.
+            0
+        } else {
+            return None;
+        };
+
+        Some(WriteGrant {
+            prod: self,
+            start,
+            len,
+        })
+    }
+
+    /// Get the largest contiguous region of free bytes, up to `max` bytes, if there is one.
+    pub fn grant_max_remaining(&mut self, max: usize) -> Option<WriteGrant<'_, 'a, N>> {
+        let write = self.bbq.write.load(Ordering::Relaxed);
+        let read = self.bbq.read.load(Ordering::Acquire);
+
+        let (start, len) = if write < read {
+            (write, min(read - write - 1, max))
+        } else if write != N {
+            (write, min(N - write, max))
+        } else {
+            (0, min(read.saturating_sub(1), max))
+        };
+
+        if len == 0 {
+            return None;
+        }
+
+        Some(WriteGrant {
+            prod: self,
+            start,
+            len,
+        })
+    }
+
+    /// Copy as many bytes of `buf` as there is room for into the buffer, commit them, and return
+    /// how many were copied.
+    ///
+    /// Bytes are written across the end of the buffer if needed.
+    pub fn write_bytes(&mut self, buf: &[u8]) -> usize {
+        let mut copied = 0;
+        while copied < buf.len() {
+            let Some(mut grant) = self.grant_max_remaining(buf.len() - copied) else {
+                break;
+            };
+            let len = grant.len();
+            grant.copy_from_slice(&buf[copied..copied + len]);
+            grant.commit(len);
+            copied += len;
+        }
+        copied
+    }
+}
+
+/// Safety: Each region is only ever accessed by the handle that owns it, as gated by the
+/// `read`/`write` positions.
+unsafe impl<'a, const N: usize> Send for Producer<'a, N> {}
+
+/// Writes formatted text into the buffer, failing if there is not enough room for all of it.
+/// The text that did fit is committed.
+impl<'a, const N: usize> fmt::Write for Producer<'a, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.write_bytes(s.as_bytes()) == s.len() {
+            Ok(())
+        } else {
+            Err(fmt::Error)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a, const N: usize> embedded_io::ErrorType for Producer<'a, N> {
+    type Error = core::convert::Infallible;
+}
+
+/// Busy-waits for free bytes, and commits as many bytes of `buf` as there is room for.
+#[cfg(feature = "embedded-io")]
+impl<'a, const N: usize> embedded_io::Write for Producer<'a, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        loop {
+            match self.write_bytes(buf) {
+                0 if !buf.is_empty() => hint::spin_loop(),
+                n => return Ok(n),
+            }
+        }
+    }
+
+    /// Committed bytes are readable right away, so there is nothing to flush.
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<'a, const N: usize> embedded_io::WriteReady for Producer<'a, N> {
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.grant_max_remaining(1).is_some())
+    }
+}
+
+/// Waits for free bytes, and commits as many bytes of `buf` as there is room for.
+#[cfg(feature = "embedded-io-async")]
+impl<'a, const N: usize> embedded_io_async::Write for Producer<'a, N> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        core::future::poll_fn(|cx| {
+            let n = self.write_bytes(buf);
+            if n > 0 || buf.is_empty() {
+                return Poll::Ready(Ok(n));
+            }
+
+            self.bbq.tx_waker.register(cx.waker());
+
+            // Check again, in case bytes were released before the waker was registered.
+            match self.write_bytes(buf) {
+                0 => Poll::Pending,
+                n => Poll::Ready(Ok(n)),
+            }
+        })
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Contiguous region of free bytes, returned by [`Producer::grant_exact`] and
+/// [`Producer::grant_max_remaining`].
+///
+/// Dropping the grant without [`commit`](WriteGrant::commit)ting it leaves the buffer as it was.
+pub struct WriteGrant<'b, 'a, const N: usize> {
+    prod: &'b mut Producer<'a, N>,
+    start: usize,
+    len: usize,
+}
+
+impl<'b, 'a, const N: usize> WriteGrant<'b, 'a, N> {
+    /// Make the first `used` bytes of the region available to the consumer. `used` is clamped
+    /// to the length of the region.
+    pub fn commit(self, used: usize) {
+        let used = min(used, self.len);
+        if used == 0 {
+            return;
+        }
+
+        let bbq = self.prod.bbq;
+        let write = bbq.write.load(Ordering::Relaxed);
+        let last = bbq.last.load(Ordering::Relaxed);
+        let new_write = self.start + used;
+
+        if new_write < write && write != N {
+            // Wrapped around, leaving the bytes between `write` and the end unused.
+            bbq.last.store(write, Ordering::Release);
+        } else if new_write > last {
+            // Passed the previous end of the readable bytes, so the whole buffer is in use again.
+            bbq.last.store(N, Ordering::Release);
+        }
+
+        bbq.write.store(new_write, Ordering::Release);
+        #[cfg(feature = "async")]
+        bbq.rx_waker.wake();
+    }
+}
+
+impl<'b, 'a, const N: usize> Deref for WriteGrant<'b, 'a, N> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        // SAFETY: The region is owned by the producer until it is committed.
+        unsafe { &*self.prod.bbq.region(self.start, self.len) }
+    }
+}
+
+impl<'b, 'a, const N: usize> DerefMut for WriteGrant<'b, 'a, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: The region is owned by the producer until it is committed.
+        unsafe { &mut *self.prod.bbq.region(self.start, self.len) }
+    }
+}
+
+/// Safety: The region stays where it is in the buffer when the grant is moved, and is owned by the
+/// producer until it is committed.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<'b, 'a, const N: usize> embedded_dma::WriteBuffer for WriteGrant<'b, 'a, N> {
+    type Word = u8;
+
+    #[inline]
+    unsafe fn write_buffer(&mut self) -> (*mut u8, usize) {
+        (self.prod.bbq.region(self.start, self.len).cast(), self.len)
+    }
+}
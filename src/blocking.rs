@@ -0,0 +1,92 @@
+//! Blocking iteration for `std` targets, enabled by the `std` feature, e.g. for host-side
+//! simulations of a firmware pipeline.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::SingleSlotQueue;
+//! use std::thread;
+//!
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! let (mut cons, mut prod) = queue.split();
+//!
+//! thread::scope(|scope| {
+//!     scope.spawn(move || {
+//!         for i in 0..3 {
+//!             while prod.enqueue(i).is_some() {
+//!                 thread::yield_now();
+//!             }
+//!         }
+//!     });
+//!
+//!     // Ends once the producer is dropped.
+//!     assert!(cons.iter_blocking().collect::<Vec<_>>() == [0, 1, 2]);
+//! });
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    Consumer,
+};
+use core::task::{Context, Poll, Waker};
+use std::{
+    sync::Arc,
+    task::Wake,
+    thread::{self, Thread},
+};
+
+impl<'a, T, S: Storage<T>> Consumer<'a, T, S> {
+    /// Iterator dequeuing values, parking the current thread while the queue is empty.
+    ///
+    /// The iterator ends once the corresponding [`Producer`](crate::Producer) has been dropped
+    /// and the queue drained.
+    pub fn iter_blocking(&mut self) -> BlockingIter<'_, 'a, T, S> {
+        BlockingIter {
+            cons: self,
+            waker: Arc::new(Unparker(thread::current())).into(),
+        }
+    }
+}
+
+/// Wakes a parked thread.
+struct Unparker(Thread);
+
+impl Wake for Unparker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Iterator returned by [`Consumer::iter_blocking`].
+///
+/// Dropping the iterator deregisters its thread from the queue.
+pub struct BlockingIter<'b, 'a, T, S: Storage<T> = Inline<T>> {
+    cons: &'b mut Consumer<'a, T, S>,
+    /// Unparks the thread the iterator was created on.
+    waker: Waker,
+}
+
+impl<'b, 'a, T, S: Storage<T>> Iterator for BlockingIter<'b, 'a, T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut cx = Context::from_waker(&self.waker);
+        loop {
+            match self.cons.poll_dequeue(&mut cx) {
+                Poll::Ready(r) => return r.ok(),
+                // Spurious wakeups just poll again.
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+}
+
+impl<'b, 'a, T, S: Storage<T>> Drop for BlockingIter<'b, 'a, T, S> {
+    fn drop(&mut self) {
+        self.cons.ssq.rx_waker.clear();
+    }
+}
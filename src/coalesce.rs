@@ -0,0 +1,52 @@
+//! Producer adapter merging new values into unread ones, instead of overwriting them.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{coalesce::Coalescing, SingleSlotQueue};
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! let (mut cons, prod) = queue.split();
+//!
+//! // Count events, even those the consumer has not gotten to yet.
+//! let mut prod = Coalescing::new(prod, |old, new| old + new);
+//! prod.enqueue(1);
+//! prod.enqueue(2);
+//! assert!(cons.dequeue() == Some(3));
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    Producer,
+};
+
+/// Producer which merges every value it enqueues into the unread value in the queue, if any,
+/// with a merge function configured once for the queue.
+pub struct Coalescing<'a, T, F, S: Storage<T> = Inline<T>> {
+    prod: Producer<'a, T, S>,
+    merge: F,
+}
+
+impl<'a, T, F, S> Coalescing<'a, T, F, S>
+where
+    F: FnMut(T, T) -> T,
+    S: Storage<T>,
+{
+    /// Wrap `prod`, merging values with `merge(old, new)`.
+    #[inline]
+    pub fn new(prod: Producer<'a, T, S>, merge: F) -> Self {
+        Coalescing { prod, merge }
+    }
+
+    /// Write a value into the queue, merging it into the old value if it exists, as with
+    /// [`Producer::enqueue_merge`].
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        self.prod.enqueue_merge(val, &mut self.merge)
+    }
+
+    /// Give back the wrapped producer.
+    #[inline]
+    pub fn into_inner(self) -> Producer<'a, T, S> {
+        self.prod
+    }
+}
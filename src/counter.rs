@@ -0,0 +1,133 @@
+//! Counting event signal, so that events signalled before the listener gets to them are counted
+//! instead of collapsing into one.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::counter::Counter;
+//! static TICKS: Counter = Counter::new();
+//!
+//! // Typically called from an interrupt handler.
+//! TICKS.increment();
+//! TICKS.increment();
+//!
+//! assert!(TICKS.take() == 2);
+//! assert!(TICKS.take() == 0);
+//! ```
+
+#[cfg(feature = "async")]
+use atomic_polyfill::AtomicBool;
+use atomic_polyfill::{AtomicUsize, Ordering};
+#[cfg(feature = "async")]
+use core::task::{Context, Poll};
+
+#[cfg(feature = "async")]
+use crate::waker::WakerCell;
+
+/// Event count which any number of producers may increment, and a single listener takes.
+pub struct Counter {
+    count: AtomicUsize,
+    #[cfg(feature = "async")]
+    waker: WakerCell,
+    /// Set while a [`Listener`] exists.
+    #[cfg(feature = "async")]
+    listening: AtomicBool,
+}
+
+impl Counter {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Counter {
+            count: AtomicUsize::new(0),
+            #[cfg(feature = "async")]
+            waker: WakerCell::new(),
+            #[cfg(feature = "async")]
+            listening: AtomicBool::new(false),
+        }
+    }
+
+    /// Count one event.
+    #[inline]
+    pub fn increment(&self) {
+        self.add(1);
+    }
+
+    /// Count `n` events, waking the listener if it is waiting. The count wraps around on
+    /// overflow.
+    #[inline]
+    pub fn add(&self, n: usize) {
+        self.count.fetch_add(n, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.waker.wake();
+    }
+
+    /// Take all the events counted so far, resetting the count to 0.
+    #[inline]
+    pub fn take(&self) -> usize {
+        self.count.swap(0, Ordering::Acquire)
+    }
+
+    /// The number of events counted so far, without resetting the count.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Claim the right to wait for events, for the single task listening on the counter.
+    ///
+    /// Returns `None` if there already is a listener; it can be claimed again once that one is
+    /// dropped.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn listen(&self) -> Option<Listener<'_>> {
+        self.listening
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Listener { counter: self })
+    }
+}
+
+/// Handle of the task waiting on a [`Counter`], returned by [`Counter::listen`].
+///
+/// There is at most one listener per counter, so it is always the one woken by an event.
+#[cfg(feature = "async")]
+pub struct Listener<'a> {
+    counter: &'a Counter,
+}
+
+#[cfg(feature = "async")]
+impl<'a> Listener<'a> {
+    /// Wait for at least one event, and take all the events counted so far.
+    pub async fn wait(&mut self) -> usize {
+        core::future::poll_fn(|cx| self.poll_wait(cx)).await
+    }
+
+    /// Poll for events, for use in hand-written futures.
+    ///
+    /// Takes all the events and returns `Poll::Ready(n)` if at least one was counted.
+    /// Otherwise, registers the task's waker to be woken by the next event, and returns
+    /// `Poll::Pending`.
+    pub fn poll_wait(&mut self, cx: &mut Context<'_>) -> Poll<usize> {
+        let counter = self.counter;
+        match counter.take() {
+            0 => {}
+            n => return Poll::Ready(n),
+        }
+
+        counter.waker.register(cx.waker());
+
+        // Check again, in case an event was counted before the waker was registered.
+        match counter.take() {
+            0 => Poll::Pending,
+            n => Poll::Ready(n),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a> Drop for Listener<'a> {
+    fn drop(&mut self) {
+        self.counter.waker.clear();
+        self.counter.listening.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,118 @@
+//! Consumer adapter which only yields a value once it has stayed unchanged for a settle time,
+//! e.g. for button or limit switch events fed from interrupt handlers.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{debounce::Debounced, timestamp::Clock, SingleSlotQueue};
+//! use std::cell::Cell;
+//!
+//! struct FakeClock(Cell<u32>);
+//! impl Clock for FakeClock {
+//!     type Instant = u32;
+//!     fn now(&self) -> u32 {
+//!         self.0.get()
+//!     }
+//!     // Tick counters wrap around.
+//!     fn duration_since(&self, later: u32, earlier: u32) -> u32 {
+//!         later.wrapping_sub(earlier)
+//!     }
+//! }
+//!
+//! let mut queue = SingleSlotQueue::<bool>::new();
+//! let (cons, mut prod) = queue.split();
+//! let mut button = Debounced::new(cons, FakeClock(Cell::new(0)), 10);
+//!
+//! prod.enqueue(true);
+//! assert!(button.dequeue() == None);
+//!
+//! // The switch bounced, which restarts the settle time.
+//! button.clock().0.set(5);
+//! prod.enqueue(false);
+//! assert!(button.dequeue() == None);
+//! prod.enqueue(true);
+//! assert!(button.dequeue() == None);
+//!
+//! button.clock().0.set(15);
+//! assert!(button.dequeue() == Some(true));
+//! assert!(button.dequeue() == None);
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    timestamp::Clock,
+    Consumer,
+};
+use core::ops::Sub;
+
+/// Consumer yielding values once they have stayed unchanged for a settle time.
+pub struct Debounced<'a, T, C, S = Inline<T>>
+where
+    C: Clock,
+    C::Instant: Sub,
+    S: Storage<T>,
+{
+    cons: Consumer<'a, T, S>,
+    clock: C,
+    settle: <C::Instant as Sub>::Output,
+    /// The latest value dequeued, and when it first came in.
+    pending: Option<(T, C::Instant)>,
+    /// The latest value yielded.
+    output: Option<T>,
+}
+
+impl<'a, T, C, S> Debounced<'a, T, C, S>
+where
+    T: Clone + PartialEq,
+    C: Clock,
+    C::Instant: Sub,
+    <C::Instant as Sub>::Output: PartialOrd,
+    S: Storage<T>,
+{
+    /// Wrap `cons`, yielding values once they have stayed unchanged for `settle`, as measured by
+    /// `clock`.
+    #[inline]
+    pub fn new(cons: Consumer<'a, T, S>, clock: C, settle: <C::Instant as Sub>::Output) -> Self {
+        Debounced {
+            cons,
+            clock,
+            settle,
+            pending: None,
+            output: None,
+        }
+    }
+
+    /// Dequeue any new value, and return the latest one if it has settled since it was last
+    /// returned.
+    ///
+    /// This needs to be called regularly, even when no new values are enqueued, for a value to
+    /// be returned once it has settled.
+    pub fn dequeue(&mut self) -> Option<T> {
+        let now = self.clock.now();
+        if let Some(v) = self.cons.dequeue() {
+            if self.pending.as_ref().is_none_or(|(p, _)| *p != v) {
+                self.pending = Some((v, now));
+            }
+        }
+
+        let (v, since) = self.pending.as_ref()?;
+        if self.clock.duration_since(now, *since) < self.settle || self.output.as_ref() == Some(v) {
+            return None;
+        }
+
+        self.output = Some(v.clone());
+        self.output.clone()
+    }
+
+    /// The clock used to measure the settle time.
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Give back the wrapped consumer.
+    #[inline]
+    pub fn into_inner(self) -> Consumer<'a, T, S> {
+        self.cons
+    }
+}
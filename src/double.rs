@@ -0,0 +1,150 @@
+//! Double buffer with an explicit swap, for snapshots that are built incrementally.
+//!
+//! The producer fills the back buffer in place for as long as it needs to, then swaps it with
+//! the front buffer. The consumer only ever sees the front buffer, which never changes while it
+//! is being read.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::double::DoubleBuffer;
+//! let mut buf = DoubleBuffer::new([0u8; 4]);
+//! let (mut cons, mut prod) = buf.split();
+//!
+//! prod.back_buffer()[0] = 1;
+//! prod.back_buffer()[1] = 2;
+//! assert!(*cons.read() == [0; 4]);
+//!
+//! assert!(prod.swap());
+//! assert!(*cons.read() == [1, 2, 0, 0]);
+//!
+//! // The swap fails while the consumer is reading the front buffer.
+//! let front = cons.read();
+//! assert!(!prod.swap());
+//! drop(front);
+//! assert!(prod.swap());
+//! ```
+
+use atomic_polyfill::{AtomicU8, Ordering};
+use core::{cell::UnsafeCell, ops::Deref};
+
+/// Index of the front buffer.
+const FRONT: u8 = 0b001;
+/// Set while the consumer is reading the front buffer.
+const READING: u8 = 0b010;
+/// Set when the front buffer has been swapped in since the consumer last read it.
+const UPDATED: u8 = 0b100;
+
+/// Double buffer with one [`Producer`] and one [`Consumer`].
+pub struct DoubleBuffer<T> {
+    bufs: [UnsafeCell<T>; 2],
+    state: AtomicU8,
+}
+
+impl<T> DoubleBuffer<T> {
+    /// Create a double buffer whose two buffers start out holding `init`.
+    pub fn new(init: T) -> Self
+    where
+        T: Clone,
+    {
+        DoubleBuffer {
+            bufs: [UnsafeCell::new(init.clone()), UnsafeCell::new(init)],
+            state: AtomicU8::new(0),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (Consumer { buf: self }, Producer { buf: self })
+    }
+}
+
+/// Read handle to a double buffer.
+pub struct Consumer<'a, T> {
+    buf: &'a DoubleBuffer<T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Check if the front buffer has been swapped since it was last read.
+    #[inline]
+    pub fn is_updated(&self) -> bool {
+        self.buf.state.load(Ordering::Relaxed) & UPDATED != 0
+    }
+
+    /// Borrow the front buffer.
+    ///
+    /// This method never blocks. The [`Producer`] cannot swap the buffers for as long as the
+    /// returned guard is alive.
+    #[inline]
+    pub fn read(&mut self) -> Front<'_, 'a, T> {
+        let state = self.buf.state.fetch_or(READING, Ordering::Acquire);
+        self.buf.state.fetch_and(!UPDATED, Ordering::Relaxed);
+        Front {
+            cons: self,
+            idx: state & FRONT,
+        }
+    }
+}
+
+/// Safety: Each buffer is only ever accessed by one handle at a time, as gated by `state`.
+unsafe impl<'a, T: Send> Send for Consumer<'a, T> {}
+
+/// Borrow of the front buffer, returned by [`Consumer::read`].
+pub struct Front<'b, 'a, T> {
+    cons: &'b mut Consumer<'a, T>,
+    idx: u8,
+}
+
+impl<'b, 'a, T> Deref for Front<'b, 'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: The producer does not swap the buffers while `READING` is set.
+        unsafe { &*self.cons.buf.bufs[usize::from(self.idx)].get() }
+    }
+}
+
+impl<'b, 'a, T> Drop for Front<'b, 'a, T> {
+    fn drop(&mut self) {
+        self.cons.buf.state.fetch_and(!READING, Ordering::Release);
+    }
+}
+
+/// Write handle to a double buffer.
+pub struct Producer<'a, T> {
+    buf: &'a DoubleBuffer<T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Get the back buffer, to build the next value in place.
+    ///
+    /// After a swap, the back buffer holds the value that was previously in front.
+    #[inline]
+    pub fn back_buffer(&mut self) -> &mut T {
+        let back = !self.buf.state.load(Ordering::Relaxed) & FRONT;
+        // SAFETY: Only the producer changes which buffer is in front, and the consumer never
+        // reads the back buffer.
+        unsafe { &mut *self.buf.bufs[usize::from(back)].get() }
+    }
+
+    /// Swap the back buffer to the front.
+    ///
+    /// This method never blocks. If the [`Consumer`] is in the middle of reading the front
+    /// buffer, the buffers are left as they are and `false` is returned.
+    #[inline]
+    pub fn swap(&mut self) -> bool {
+        let state = self.buf.state.load(Ordering::Relaxed);
+        if state & READING != 0 {
+            return false;
+        }
+
+        let swapped = (state ^ FRONT) | UPDATED;
+        self.buf
+            .state
+            .compare_exchange(state, swapped, Ordering::AcqRel, Ordering::Relaxed)
+            .is_ok()
+    }
+}
+
+/// Safety: Each buffer is only ever accessed by one handle at a time, as gated by `state`.
+unsafe impl<'a, T: Send> Send for Producer<'a, T> {}
@@ -0,0 +1,82 @@
+//! Bidirectional channel made of two single slot queues, for ping-pong protocols between
+//! cores or tasks.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::duplex::Duplex;
+//! let mut duplex = Duplex::<u32, &str>::new();
+//! let (mut a, mut b) = duplex.split();
+//!
+//! assert!(a.enqueue(1) == None);
+//! assert!(b.dequeue() == Some(1));
+//!
+//! assert!(b.enqueue("pong") == None);
+//! assert!(a.dequeue() == Some("pong"));
+//! ```
+
+use crate::{Consumer, Producer, SingleSlotQueue};
+
+/// Channel carrying `T`s one way, and `U`s the other.
+pub struct Duplex<T, U> {
+    forward: SingleSlotQueue<T>,
+    backward: SingleSlotQueue<U>,
+}
+
+impl<T, U> Duplex<T, U> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Duplex {
+            forward: SingleSlotQueue::new(),
+            backward: SingleSlotQueue::new(),
+        }
+    }
+
+    /// Split the channel into the endpoint sending `T`s, and the endpoint sending `U`s.
+    pub fn split(&mut self) -> (Endpoint<'_, T, U>, Endpoint<'_, U, T>) {
+        let (fwd_cons, fwd_prod) = self.forward.split();
+        let (bwd_cons, bwd_prod) = self.backward.split();
+        (
+            Endpoint {
+                rx: bwd_cons,
+                tx: fwd_prod,
+            },
+            Endpoint {
+                rx: fwd_cons,
+                tx: bwd_prod,
+            },
+        )
+    }
+}
+
+/// One end of a duplex channel, sending `Tx`s and receiving `Rx`s.
+pub struct Endpoint<'a, Tx, Rx> {
+    rx: Consumer<'a, Rx>,
+    tx: Producer<'a, Tx>,
+}
+
+impl<'a, Tx, Rx> Endpoint<'a, Tx, Rx> {
+    /// Write a value for the other endpoint, as with [`Producer::enqueue`].
+    #[inline]
+    pub fn enqueue(&mut self, val: Tx) -> Option<Tx> {
+        self.tx.enqueue(val)
+    }
+
+    /// Read a value from the other endpoint, as with [`Consumer::dequeue`].
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<Rx> {
+        self.rx.dequeue()
+    }
+
+    /// Check if the other endpoint is still around.
+    #[inline]
+    pub fn is_peer_connected(&self) -> bool {
+        self.tx.is_consumer_connected()
+    }
+
+    /// Borrow the underlying queue handles, e.g. to use their async methods.
+    #[inline]
+    pub fn as_parts(&mut self) -> (&mut Consumer<'a, Rx>, &mut Producer<'a, Tx>) {
+        (&mut self.rx, &mut self.tx)
+    }
+}
@@ -0,0 +1,43 @@
+//! Timeout variants of the async methods, using [`embassy_time`].
+
+use crate::{
+    future::{DequeueTimeoutError, EnqueueTimeoutError},
+    storage::Storage,
+    Closed, Consumer, Disconnected, Producer,
+};
+use core::future::poll_fn;
+use embassy_time::{with_timeout, Duration, TimeoutError};
+
+impl<'a, T, S: Storage<T>> Consumer<'a, T, S> {
+    /// Like [`dequeue_async`](Consumer::dequeue_async), but gives up with
+    /// `Err(DequeueTimeoutError::Timeout)` if no value was enqueued within `timeout`.
+    pub async fn dequeue_timeout(&mut self, timeout: Duration) -> Result<T, DequeueTimeoutError> {
+        match with_timeout(timeout, self.dequeue_async()).await {
+            Ok(Ok(v)) => Ok(v),
+            Ok(Err(Closed)) => Err(DequeueTimeoutError::Closed),
+            Err(TimeoutError) => Err(DequeueTimeoutError::Timeout),
+        }
+    }
+}
+
+impl<'a, T, S: Storage<T>> Producer<'a, T, S> {
+    /// Like [`enqueue_async`](Producer::enqueue_async), but gives up with
+    /// `Err(EnqueueTimeoutError::Timeout(val))` if the queue did not become empty within
+    /// `timeout`.
+    pub async fn enqueue_timeout(
+        &mut self,
+        val: T,
+        timeout: Duration,
+    ) -> Result<(), EnqueueTimeoutError<T>> {
+        let mut val = Some(val);
+        match with_timeout(timeout, poll_fn(|cx| self.poll_enqueue(cx, &mut val))).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(Disconnected(v))) => Err(EnqueueTimeoutError::Disconnected(v)),
+            Err(TimeoutError) => {
+                self.ssq.tx_waker.clear();
+                // `poll_enqueue` only takes the value out when it resolves.
+                Err(EnqueueTimeoutError::Timeout(val.take().unwrap()))
+            }
+        }
+    }
+}
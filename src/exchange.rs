@@ -0,0 +1,160 @@
+//! Exchanger swapping values between two parties, for ownership round-trips such as trading a
+//! filled DMA buffer for an empty one.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::exchange::Exchanger;
+//! use std::thread;
+//!
+//! let mut exchanger = Exchanger::<&str>::new();
+//! let (mut a, mut b) = exchanger.split();
+//!
+//! thread::scope(|scope| {
+//!     scope.spawn(move || {
+//!         assert!(b.exchange("empty") == Ok("filled"));
+//!     });
+//!     assert!(a.exchange("filled") == Ok("empty"));
+//! });
+//! ```
+
+use crate::{slot::RawSlot, Disconnected};
+use atomic_polyfill::{AtomicBool, Ordering};
+use core::hint;
+
+#[cfg(feature = "async")]
+use crate::waker::WakerCell;
+
+/// Exchanger between two [`Party`] handles.
+pub struct Exchanger<T> {
+    /// The value offered by each party.
+    offers: [RawSlot<T>; 2],
+    /// Set when each party is dropped.
+    closed: [AtomicBool; 2],
+    #[cfg(feature = "async")]
+    wakers: [WakerCell; 2],
+}
+
+impl<T> Exchanger<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Exchanger {
+            offers: [RawSlot::new(), RawSlot::new()],
+            closed: [AtomicBool::new(false), AtomicBool::new(false)],
+            #[cfg(feature = "async")]
+            wakers: [WakerCell::new(), WakerCell::new()],
+        }
+    }
+
+    pub fn split(&mut self) -> (Party<'_, T>, Party<'_, T>) {
+        *self.closed[0].get_mut() = false;
+        *self.closed[1].get_mut() = false;
+        (Party { ex: self, id: 0 }, Party { ex: self, id: 1 })
+    }
+}
+
+/// One of the two parties of an exchanger.
+pub struct Party<'a, T> {
+    ex: &'a Exchanger<T>,
+    id: usize,
+}
+
+impl<'a, T> Party<'a, T> {
+    /// Offer a value, and busy-wait for the value offered by the other party.
+    ///
+    /// If the other party is dropped before taking the value, it is returned in
+    /// `Err(Disconnected(_))`.
+    pub fn exchange(&mut self, val: T) -> Result<T, Disconnected<T>> {
+        let mut val = Some(val);
+        loop {
+            if let Some(r) = self.poll_exchange(&mut val) {
+                return r;
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Offer a value, and wait for the value offered by the other party.
+    ///
+    /// If the other party is dropped before taking the value, it is returned in
+    /// `Err(Disconnected(_))`. If the returned future is dropped after the value was offered,
+    /// the value stays on offer, and the next exchange returns the other party's value for it.
+    #[cfg(feature = "async")]
+    pub async fn exchange_async(&mut self, val: T) -> Result<T, Disconnected<T>> {
+        let mut val = Some(val);
+        core::future::poll_fn(|cx| {
+            if let Some(r) = self.poll_exchange(&mut val) {
+                return core::task::Poll::Ready(r);
+            }
+
+            self.ex.wakers[self.id].register(cx.waker());
+
+            // Check again, in case the other party made progress before the waker was
+            // registered.
+            match self.poll_exchange(&mut val) {
+                Some(r) => core::task::Poll::Ready(r),
+                None => core::task::Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Check if the other party is still around.
+    #[inline]
+    pub fn is_peer_connected(&self) -> bool {
+        !self.ex.closed[self.peer()].load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn peer(&self) -> usize {
+        1 - self.id
+    }
+
+    /// Move the exchange forward: offer `val` if it has not been offered yet, then try taking
+    /// the other party's value. Returns `None` if the exchange is not complete yet.
+    fn poll_exchange(&mut self, val: &mut Option<T>) -> Option<Result<T, Disconnected<T>>> {
+        let (ours, theirs) = (&self.ex.offers[self.id], &self.ex.offers[self.peer()]);
+
+        if let Some(v) = val.take() {
+            // Our previous offer may not have been taken yet.
+            if let Some(v) = ours.put(v) {
+                if !self.is_peer_connected() {
+                    return Some(Err(Disconnected(v)));
+                }
+                *val = Some(v);
+                return None;
+            }
+            self.wake_peer();
+        }
+
+        if let Some(t) = theirs.take() {
+            self.wake_peer();
+            return Some(Ok(t));
+        }
+
+        if !self.is_peer_connected() {
+            // The other party may have offered a value right before being dropped.
+            if let Some(t) = theirs.take() {
+                return Some(Ok(t));
+            }
+            if let Some(v) = ours.take() {
+                return Some(Err(Disconnected(v)));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn wake_peer(&self) {
+        #[cfg(feature = "async")]
+        self.ex.wakers[self.peer()].wake();
+    }
+}
+
+impl<'a, T> Drop for Party<'a, T> {
+    fn drop(&mut self) {
+        self.ex.closed[self.id].store(true, Ordering::Release);
+        self.wake_peer();
+    }
+}
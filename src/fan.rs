@@ -0,0 +1,181 @@
+//! Combinators spreading values over several queues, or gathering them from several queues.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{fan::FanOut, SingleSlotQueue};
+//! let mut a = SingleSlotQueue::<u32>::new();
+//! let mut b = SingleSlotQueue::<u32>::new();
+//! let (mut cons_a, prod_a) = a.split();
+//! let (mut cons_b, prod_b) = b.split();
+//! let mut fan_out = FanOut::new([prod_a, prod_b]);
+//!
+//! // Every consumer gets its own copy.
+//! assert!(fan_out.enqueue(1) == 2);
+//! assert!(cons_a.dequeue() == Some(1));
+//! assert!(cons_b.dequeue() == Some(1));
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    Consumer, Producer,
+};
+
+/// Producer writing every value into `N` queues, e.g. so that one interrupt handler can notify
+/// several independent consumers.
+pub struct FanOut<'a, T, const N: usize, S: Storage<T> = Inline<T>> {
+    prods: [Producer<'a, T, S>; N],
+}
+
+impl<'a, T: Clone, const N: usize, S: Storage<T>> FanOut<'a, T, N, S> {
+    pub fn new(prods: [Producer<'a, T, S>; N]) -> Self {
+        FanOut { prods }
+    }
+
+    /// Write a copy of the value into each queue, as with [`Producer::enqueue`], and return the
+    /// number of queues it was written to. Copies that could not be written are dropped.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> usize {
+        self.for_each(val, |prod, val| prod.enqueue(val).is_none())
+    }
+
+    /// Write a copy of the value into each queue, overwriting the old value if it exists, as
+    /// with [`Producer::enqueue_overwrite`], and return the number of queues it was written to.
+    /// Copies that could not be written, and overwritten values, are dropped.
+    #[inline]
+    pub fn enqueue_overwrite(&mut self, val: T) -> usize {
+        self.for_each(val, |prod, val| prod.try_enqueue_overwrite(val).is_ok())
+    }
+
+    /// Unwrap the underlying producers.
+    #[inline]
+    pub fn into_inner(self) -> [Producer<'a, T, S>; N] {
+        self.prods
+    }
+
+    /// Hand a copy of `val` to `write` for each producer, moving the original into the last
+    /// one, and count how many times it returned `true`.
+    fn for_each(
+        &mut self,
+        val: T,
+        mut write: impl FnMut(&mut Producer<'a, T, S>, T) -> bool,
+    ) -> usize {
+        let Some((last, rest)) = self.prods.split_last_mut() else {
+            return 0;
+        };
+
+        let mut written = 0;
+        for prod in rest {
+            written += usize::from(write(prod, val.clone()));
+        }
+        written + usize::from(write(last, val))
+    }
+}
+
+/// Consumer reading from `N` queues in turn, so that a single task can service several sources
+/// from one call site.
+pub struct FanIn<'a, T, const N: usize, S: Storage<T> = Inline<T>> {
+    conss: [Consumer<'a, T, S>; N],
+    /// Index of the queue to try first on the next read.
+    next: usize,
+}
+
+impl<'a, T, const N: usize, S: Storage<T>> FanIn<'a, T, N, S> {
+    pub fn new(conss: [Consumer<'a, T, S>; N]) -> Self {
+        FanIn { conss, next: 0 }
+    }
+
+    /// Try reading a value from any of the queues, and return it along with the index of the
+    /// queue it came from.
+    ///
+    /// The queues are tried round-robin, starting after the one the last value came from, so a
+    /// busy queue cannot starve the others. Like [`Consumer::dequeue`], this never blocks.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<(usize, T)> {
+        for i in (self.next..N).chain(0..self.next) {
+            if let Some(v) = self.conss[i].dequeue() {
+                self.next = (i + 1) % N;
+                return Some((i, v));
+            }
+        }
+        None
+    }
+
+    /// Check if all of the queues are empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.conss.iter().all(Consumer::is_empty)
+    }
+
+    /// Check if the producers of all of the queues have been dropped.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.conss.iter().all(Consumer::is_closed)
+    }
+
+    /// Unwrap the underlying consumers.
+    #[inline]
+    pub fn into_inner(self) -> [Consumer<'a, T, S>; N] {
+        self.conss
+    }
+}
+
+/// Pair up the values of two queues, e.g. to fuse matched samples from two sensors.
+#[inline]
+pub fn zip<'a, A, B, SA: Storage<A>, SB: Storage<B>>(
+    a: Consumer<'a, A, SA>,
+    b: Consumer<'a, B, SB>,
+) -> Zip<'a, A, B, SA, SB> {
+    Zip {
+        a,
+        b,
+        held_a: None,
+        held_b: None,
+    }
+}
+
+/// Consumer reading pairs of values from two queues, returned by [`zip`].
+pub struct Zip<'a, A, B, SA: Storage<A> = Inline<A>, SB: Storage<B> = Inline<B>> {
+    a: Consumer<'a, A, SA>,
+    b: Consumer<'a, B, SB>,
+    /// Value read from one queue while the other's producer was in the middle of writing.
+    held_a: Option<A>,
+    held_b: Option<B>,
+}
+
+impl<'a, A, B, SA: Storage<A>, SB: Storage<B>> Zip<'a, A, B, SA, SB> {
+    /// Try reading a value from each queue, only if both have one.
+    ///
+    /// If one of the values cannot be read after the other was dequeued, because its producer
+    /// started writing in between, the value already dequeued is held until the next call, unless
+    /// a newer value replaces it by then. Like [`Consumer::dequeue`], this never blocks.
+    pub fn dequeue(&mut self) -> Option<(A, B)> {
+        if (self.held_a.is_none() && self.a.is_empty())
+            || (self.held_b.is_none() && self.b.is_empty())
+        {
+            return None;
+        }
+
+        if let Some(v) = self.a.dequeue() {
+            self.held_a = Some(v);
+        }
+        if let Some(v) = self.b.dequeue() {
+            self.held_b = Some(v);
+        }
+
+        match (self.held_a.take(), self.held_b.take()) {
+            (Some(a), Some(b)) => Some((a, b)),
+            (a, b) => {
+                self.held_a = a;
+                self.held_b = b;
+                None
+            }
+        }
+    }
+
+    /// Unwrap the underlying consumers. A value held from one of them is dropped.
+    #[inline]
+    pub fn into_inner(self) -> (Consumer<'a, A, SA>, Consumer<'a, B, SB>) {
+        (self.a, self.b)
+    }
+}
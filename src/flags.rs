@@ -0,0 +1,137 @@
+//! Event flags, in the style of RTOS event groups: producers set bits, and the listener takes
+//! and clears them atomically.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::flags::EventFlags;
+//! const RX_DONE: u32 = 1 << 0;
+//! const TX_DONE: u32 = 1 << 1;
+//! static EVENTS: EventFlags = EventFlags::new();
+//!
+//! // Typically called from interrupt handlers.
+//! EVENTS.set(RX_DONE);
+//! EVENTS.set(TX_DONE);
+//!
+//! assert!(EVENTS.take_bits(TX_DONE) == TX_DONE);
+//! assert!(EVENTS.take() == RX_DONE);
+//! assert!(EVENTS.take() == 0);
+//! ```
+
+#[cfg(feature = "async")]
+use atomic_polyfill::AtomicBool;
+use atomic_polyfill::{AtomicU32, Ordering};
+#[cfg(feature = "async")]
+use core::task::{Context, Poll};
+
+#[cfg(feature = "async")]
+use crate::waker::WakerCell;
+
+/// 32 event flags which any number of producers may set, and a single listener takes.
+pub struct EventFlags {
+    bits: AtomicU32,
+    #[cfg(feature = "async")]
+    waker: WakerCell,
+    /// Set while a [`Listener`] exists.
+    #[cfg(feature = "async")]
+    listening: AtomicBool,
+}
+
+impl EventFlags {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        EventFlags {
+            bits: AtomicU32::new(0),
+            #[cfg(feature = "async")]
+            waker: WakerCell::new(),
+            #[cfg(feature = "async")]
+            listening: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the flags in `bits`, waking the listener if it is waiting.
+    #[inline]
+    pub fn set(&self, bits: u32) {
+        self.bits.fetch_or(bits, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.waker.wake();
+    }
+
+    /// Take all the flags set so far, clearing them.
+    #[inline]
+    pub fn take(&self) -> u32 {
+        self.bits.swap(0, Ordering::Acquire)
+    }
+
+    /// Take the flags in `mask` which are set, clearing only those.
+    #[inline]
+    pub fn take_bits(&self, mask: u32) -> u32 {
+        self.bits.fetch_and(!mask, Ordering::Acquire) & mask
+    }
+
+    /// The flags set so far, without clearing them.
+    #[inline]
+    pub fn get(&self) -> u32 {
+        self.bits.load(Ordering::Relaxed)
+    }
+
+    /// Claim the right to wait for flags, for the single task listening on the event flags.
+    ///
+    /// Returns `None` if there already is a listener; it can be claimed again once that one is
+    /// dropped.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn listen(&self) -> Option<Listener<'_>> {
+        self.listening
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Listener { flags: self })
+    }
+}
+
+/// Handle of the task waiting on [`EventFlags`], returned by [`EventFlags::listen`].
+///
+/// There is at most one listener per set of event flags, so it is always the one woken when
+/// flags are set.
+#[cfg(feature = "async")]
+pub struct Listener<'a> {
+    flags: &'a EventFlags,
+}
+
+#[cfg(feature = "async")]
+impl<'a> Listener<'a> {
+    /// Wait for any of the flags in `mask` to be set, and take the flags in `mask` which are
+    /// set.
+    pub async fn wait_any(&mut self, mask: u32) -> u32 {
+        core::future::poll_fn(|cx| self.poll_wait_any(cx, mask)).await
+    }
+
+    /// Poll for any of the flags in `mask`, for use in hand-written futures.
+    ///
+    /// Takes the flags in `mask` which are set and returns `Poll::Ready(bits)` if there is at
+    /// least one. Otherwise, registers the task's waker to be woken when flags are set, and
+    /// returns `Poll::Pending`.
+    pub fn poll_wait_any(&mut self, cx: &mut Context<'_>, mask: u32) -> Poll<u32> {
+        let flags = self.flags;
+        match flags.take_bits(mask) {
+            0 => {}
+            bits => return Poll::Ready(bits),
+        }
+
+        flags.waker.register(cx.waker());
+
+        // Check again, in case flags were set before the waker was registered.
+        match flags.take_bits(mask) {
+            0 => Poll::Pending,
+            bits => Poll::Ready(bits),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a> Drop for Listener<'a> {
+    fn drop(&mut self) {
+        self.flags.waker.clear();
+        self.flags.listening.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,255 @@
+//! Single slot holding one variable-length byte frame, for packet handoff (e.g. from a UART or
+//! radio interrupt handler).
+//!
+//! Frames are copied straight into and out of the slot, so no `[u8; MAX]` temporary is ever
+//! moved around.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::frame::{FrameError, FrameSlot};
+//! let mut slot = FrameSlot::<16>::new();
+//! let (mut cons, mut prod) = slot.split();
+//!
+//! assert!(prod.write_frame(b"hello") == Ok(()));
+//! assert!(prod.write_frame(b"world") == Err(FrameError::Full));
+//!
+//! let mut buf = [0; 16];
+//! assert!(cons.read_frame(&mut buf) == Ok(5));
+//! assert!(&buf[..5] == b"hello");
+//! assert!(cons.read_frame(&mut buf) == Err(FrameError::Empty));
+//! ```
+//!
+//! With the `postcard` feature, typed messages can be sent through the slot with
+//! [`Producer::send`] and [`Consumer::recv`].
+
+use atomic_polyfill::{AtomicBool, Ordering};
+use core::{cell::UnsafeCell, fmt};
+
+/// Error returned by the frame slot handles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum FrameError {
+    /// There is a frame already in the slot.
+    Full,
+    /// There is no frame in the slot.
+    Empty,
+    /// The frame does not fit in the slot, or in the buffer it is read into.
+    TooLong,
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FrameError::Full => "frame slot full",
+            FrameError::Empty => "frame slot empty",
+            FrameError::TooLong => "frame too long",
+        })
+    }
+}
+
+impl core::error::Error for FrameError {}
+
+/// Error returned by [`Producer::send`] and [`Consumer::recv`].
+#[cfg(feature = "postcard")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MessageError {
+    /// The frame could not be written or read. A message that does not fit in the slot is
+    /// reported as [`FrameError::TooLong`].
+    Frame(FrameError),
+    /// The message could not be serialized, or the frame could not be deserialized.
+    Postcard(postcard::Error),
+}
+
+#[cfg(feature = "postcard")]
+impl fmt::Display for MessageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageError::Frame(e) => fmt::Display::fmt(e, f),
+            MessageError::Postcard(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+#[cfg(feature = "postcard")]
+impl core::error::Error for MessageError {}
+
+#[cfg(feature = "postcard")]
+impl From<FrameError> for MessageError {
+    fn from(e: FrameError) -> Self {
+        MessageError::Frame(e)
+    }
+}
+
+#[cfg(feature = "postcard")]
+impl From<postcard::Error> for MessageError {
+    fn from(e: postcard::Error) -> Self {
+        match e {
+            postcard::Error::SerializeBufferFull => MessageError::Frame(FrameError::TooLong),
+            e => MessageError::Postcard(e),
+        }
+    }
+}
+
+/// Single slot holding one frame of up to `MAX` bytes.
+pub struct FrameSlot<const MAX: usize> {
+    /// Set when the slot holds a frame. The producer only writes to the slot while this is
+    /// clear, and the consumer only reads from it while this is set.
+    full: AtomicBool,
+    len: UnsafeCell<usize>,
+    buf: UnsafeCell<[u8; MAX]>,
+}
+
+impl<const MAX: usize> FrameSlot<MAX> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        FrameSlot {
+            full: AtomicBool::new(false),
+            len: UnsafeCell::new(0),
+            buf: UnsafeCell::new([0; MAX]),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, MAX>, Producer<'_, MAX>) {
+        (Consumer { slot: self }, Producer { slot: self })
+    }
+
+    /// The largest frame the slot can hold.
+    #[inline]
+    pub const fn max_len(&self) -> usize {
+        MAX
+    }
+}
+
+/// Read handle to a frame slot.
+pub struct Consumer<'a, const MAX: usize> {
+    slot: &'a FrameSlot<MAX>,
+}
+
+impl<'a, const MAX: usize> Consumer<'a, MAX> {
+    /// The length of the frame in the slot, if there is one.
+    #[inline]
+    pub fn frame_len(&self) -> Option<usize> {
+        if self.slot.full.load(Ordering::Acquire) {
+            // SAFETY: The producer does not touch the slot while it is full.
+            Some(unsafe { *self.slot.len.get() })
+        } else {
+            None
+        }
+    }
+
+    /// Copy the frame out of the slot into `buf`, returning its length.
+    ///
+    /// If `buf` is too short to hold the frame, it is left in the slot and
+    /// `Err(FrameError::TooLong)` is returned.
+    pub fn read_frame(&mut self, buf: &mut [u8]) -> Result<usize, FrameError> {
+        let len = self.frame_len().ok_or(FrameError::Empty)?;
+        let out = buf.get_mut(..len).ok_or(FrameError::TooLong)?;
+
+        // SAFETY: The producer does not touch the slot while it is full.
+        out.copy_from_slice(unsafe { &(&*self.slot.buf.get())[..len] });
+        self.slot.full.store(false, Ordering::Release);
+        Ok(len)
+    }
+
+    /// Run `f` against the frame in place, e.g. to deserialize it with `postcard::from_bytes`,
+    /// then free the slot and return the result of `f`.
+    pub fn read_frame_with<R>(&mut self, f: impl FnOnce(&[u8]) -> R) -> Result<R, FrameError> {
+        let len = self.frame_len().ok_or(FrameError::Empty)?;
+
+        // SAFETY: The producer does not touch the slot while it is full.
+        let r = f(unsafe { &(&*self.slot.buf.get())[..len] });
+        self.slot.full.store(false, Ordering::Release);
+        Ok(r)
+    }
+
+    /// Deserialize the frame in the slot as a `T`, and free the slot.
+    ///
+    /// A frame that cannot be deserialized is dropped, so that it does not block the ones after
+    /// it.
+    #[cfg(feature = "postcard")]
+    pub fn recv<T: serde::de::DeserializeOwned>(&mut self) -> Result<T, MessageError> {
+        Ok(self.read_frame_with(|frame| postcard::from_bytes(frame))??)
+    }
+
+    /// Check if there is a frame in the slot.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.full.load(Ordering::Relaxed)
+    }
+}
+
+/// Safety: The slot is only ever accessed by one handle at a time, as gated by `full`.
+unsafe impl<'a, const MAX: usize> Send for Consumer<'a, MAX> {}
+
+/// Write handle to a frame slot.
+pub struct Producer<'a, const MAX: usize> {
+    slot: &'a FrameSlot<MAX>,
+}
+
+impl<'a, const MAX: usize> Producer<'a, MAX> {
+    /// Copy `frame` into the slot.
+    pub fn write_frame(&mut self, frame: &[u8]) -> Result<(), FrameError> {
+        if frame.len() > MAX {
+            return Err(FrameError::TooLong);
+        }
+        if self.slot.full.load(Ordering::Acquire) {
+            return Err(FrameError::Full);
+        }
+
+        // SAFETY: The consumer does not touch the slot while it is empty.
+        unsafe {
+            (&mut *self.slot.buf.get())[..frame.len()].copy_from_slice(frame);
+            *self.slot.len.get() = frame.len();
+        }
+        self.slot.full.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Build a frame directly in the slot with `f`, e.g. to serialize a message with
+    /// `postcard::to_slice`.
+    ///
+    /// `f` is given the whole slot, and returns the length of the frame it wrote, or an error,
+    /// which is returned in the inner result and leaves the slot empty. If the returned length
+    /// does not fit in the slot, `Err(FrameError::TooLong)` is returned. `f` is not called if the
+    /// slot is full.
+    pub fn write_frame_with<E>(
+        &mut self,
+        f: impl FnOnce(&mut [u8]) -> Result<usize, E>,
+    ) -> Result<Result<(), E>, FrameError> {
+        if self.slot.full.load(Ordering::Acquire) {
+            return Err(FrameError::Full);
+        }
+
+        // SAFETY: The consumer does not touch the slot while it is empty.
+        let len = match f(unsafe { &mut *self.slot.buf.get() }) {
+            Ok(len) if len > MAX => return Err(FrameError::TooLong),
+            Ok(len) => len,
+            Err(e) => return Ok(Err(e)),
+        };
+
+        // SAFETY: The consumer does not touch the slot while it is empty.
+        unsafe { *self.slot.len.get() = len };
+        self.slot.full.store(true, Ordering::Release);
+        Ok(Ok(()))
+    }
+
+    /// Serialize `msg` directly into the slot.
+    ///
+    /// If `msg` cannot be serialized, or does not fit in the slot, the slot is left empty.
+    #[cfg(feature = "postcard")]
+    pub fn send<T: serde::Serialize + ?Sized>(&mut self, msg: &T) -> Result<(), MessageError> {
+        Ok(self.write_frame_with(|buf| postcard::to_slice(msg, buf).map(|frame| frame.len()))??)
+    }
+
+    /// Check if there is a frame in the slot.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.full.load(Ordering::Relaxed)
+    }
+}
+
+/// Safety: The slot is only ever accessed by one handle at a time, as gated by `full`.
+unsafe impl<'a, const MAX: usize> Send for Producer<'a, MAX> {}
@@ -0,0 +1,433 @@
+//! Futures returned by the async methods of the queue handles, and [`Stream`]/[`Sink`]
+//! support.
+
+use crate::{
+    storage::{Inline, Storage},
+    Closed, Consumer, Disconnected, EnqueueError, Producer,
+};
+use atomic_polyfill::Ordering;
+use core::{
+    fmt,
+    future::{Future, IntoFuture},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
+use futures_sink::Sink;
+
+/// Error returned by the timeout variants of [`Consumer::dequeue_async`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum DequeueTimeoutError {
+    /// No value was enqueued before the timeout expired.
+    Timeout,
+    /// The [`Producer`] has been dropped and the queue drained.
+    Closed,
+}
+
+impl fmt::Display for DequeueTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DequeueTimeoutError::Timeout => "timed out waiting for a value",
+            DequeueTimeoutError::Closed => "queue closed",
+        })
+    }
+}
+
+impl core::error::Error for DequeueTimeoutError {}
+
+/// Error returned by the timeout variants of [`Producer::enqueue_async`], containing the value
+/// that could not be enqueued.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum EnqueueTimeoutError<T> {
+    /// The queue did not become empty before the timeout expired.
+    Timeout(T),
+    /// The [`Consumer`] has been dropped.
+    Disconnected(T),
+}
+
+impl<T> fmt::Display for EnqueueTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EnqueueTimeoutError::Timeout(_) => "timed out waiting for room in the queue",
+            EnqueueTimeoutError::Disconnected(_) => "consumer disconnected",
+        })
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for EnqueueTimeoutError<T> {}
+
+/// Future returned by [`Consumer::dequeue_async`].
+///
+/// Resolves to the next value enqueued by the corresponding [`Producer`](crate::Producer), or
+/// to `Err(Closed)` once the producer has been dropped and the queue drained.
+///
+/// # Cancel safety
+///
+/// A value is only taken out of the queue when the future resolves, so dropping the future
+/// before it completes (e.g. when it loses a `select!`) never loses a value. Dropping the future
+/// also deregisters its waker from the queue.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DequeueFuture<'b, 'a, T, S: Storage<T> = Inline<T>> {
+    pub(crate) cons: &'b mut Consumer<'a, T, S>,
+}
+
+impl<'b, 'a, T, S: Storage<T>> Future for DequeueFuture<'b, 'a, T, S> {
+    type Output = Result<T, Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Closed>> {
+        self.get_mut().cons.poll_dequeue(cx)
+    }
+}
+
+impl<'b, 'a, T, S: Storage<T>> Drop for DequeueFuture<'b, 'a, T, S> {
+    fn drop(&mut self) {
+        self.cons.ssq.rx_waker.clear();
+    }
+}
+
+/// Same as the [`Stream`] implementation, for the nightly-only async iterator trait.
+#[cfg(feature = "unstable")]
+impl<'a, T, S: Storage<T>> core::async_iter::AsyncIterator for Consumer<'a, T, S> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.get_mut().poll_dequeue(cx).map(Result::ok)
+    }
+}
+
+/// Awaiting a mutable reference to a consumer dequeues the next value, as with
+/// [`Consumer::dequeue_async`].
+///
+/// ```
+/// # use ssq::SingleSlotQueue;
+/// # futures::executor::block_on(async {
+/// let mut queue = SingleSlotQueue::<u32>::new();
+/// let (mut cons, mut prod) = queue.split();
+///
+/// prod.enqueue(1);
+/// assert!((&mut cons).await == Ok(1));
+/// # });
+/// ```
+impl<'b, 'a, T, S: Storage<T>> IntoFuture for &'b mut Consumer<'a, T, S> {
+    type Output = Result<T, Closed>;
+    type IntoFuture = DequeueFuture<'b, 'a, T, S>;
+
+    #[inline]
+    fn into_future(self) -> DequeueFuture<'b, 'a, T, S> {
+        self.dequeue_async()
+    }
+}
+
+/// Future returned by [`Consumer::changed`].
+///
+/// Dropping the future before it completes deregisters its waker from the queue.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ChangedFuture<'b, 'a, T, S: Storage<T> = Inline<T>> {
+    pub(crate) cons: &'b mut Consumer<'a, T, S>,
+}
+
+impl<'b, 'a, T, S: Storage<T>> ChangedFuture<'b, 'a, T, S> {
+    /// Mark the current version as seen, returning `true` if it is a new one.
+    fn update(&mut self) -> bool {
+        let version = self.cons.ssq.version.load(Ordering::Acquire);
+        let changed = version != self.cons.seen;
+        self.cons.seen = version;
+        changed
+    }
+}
+
+impl<'b, 'a, T, S: Storage<T>> Future for ChangedFuture<'b, 'a, T, S> {
+    type Output = Result<(), Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        let this = self.get_mut();
+        if this.update() {
+            return Poll::Ready(Ok(()));
+        }
+
+        this.cons.ssq.rx_waker.register(cx.waker());
+
+        // Check again, in case the producer published a value before the waker was registered.
+        if this.update() {
+            return Poll::Ready(Ok(()));
+        }
+
+        if this.cons.is_closed() {
+            // The producer may have published a final value right before closing.
+            return Poll::Ready(if this.update() { Ok(()) } else { Err(Closed) });
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<'b, 'a, T, S: Storage<T>> Drop for ChangedFuture<'b, 'a, T, S> {
+    fn drop(&mut self) {
+        self.cons.ssq.rx_waker.clear();
+    }
+}
+
+/// The consumer is a stream of the values enqueued by the corresponding [`Producer`], which ends
+/// once the producer has been dropped and the queue drained.
+impl<'a, T, S: Storage<T>> Stream for Consumer<'a, T, S> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.get_mut().poll_dequeue(cx).map(Result::ok)
+    }
+}
+
+/// Value returned by [`select2`], from whichever consumer produced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum Either<A, B> {
+    /// Value from the first consumer.
+    First(A),
+    /// Value from the second consumer.
+    Second(B),
+}
+
+/// Wait for either of two consumers to produce a value, and dequeue it.
+///
+/// The future alternates which consumer it checks first every time it is polled, so that neither
+/// can starve the other. It resolves to `Err(Closed)` once both producers have been dropped and both
+/// queues drained. Like [`Consumer::dequeue_async`], it only takes a value out of a queue when it
+/// resolves.
+#[inline]
+pub fn select2<'b, 'a, 'c, A, B, SA: Storage<A>, SB: Storage<B>>(
+    a: &'b mut Consumer<'a, A, SA>,
+    b: &'b mut Consumer<'c, B, SB>,
+) -> Select2Future<'b, 'a, 'c, A, B, SA, SB> {
+    Select2Future {
+        a,
+        b,
+        b_first: false,
+    }
+}
+
+/// Future returned by [`select2`].
+///
+/// Dropping the future before it completes deregisters its waker from both queues.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Select2Future<'b, 'a, 'c, A, B, SA: Storage<A> = Inline<A>, SB: Storage<B> = Inline<B>> {
+    a: &'b mut Consumer<'a, A, SA>,
+    b: &'b mut Consumer<'c, B, SB>,
+    /// Check `b` first on the next poll.
+    b_first: bool,
+}
+
+impl<'b, 'a, 'c, A, B, SA: Storage<A>, SB: Storage<B>> Future
+    for Select2Future<'b, 'a, 'c, A, B, SA, SB>
+{
+    type Output = Result<Either<A, B>, Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let b_first = this.b_first;
+        this.b_first = !b_first;
+        let mut closed = 0;
+        for i in 0..2 {
+            let poll = if (i == 1) != b_first {
+                this.b.poll_dequeue(cx).map_ok(Either::Second)
+            } else {
+                this.a.poll_dequeue(cx).map_ok(Either::First)
+            };
+            match poll {
+                Poll::Ready(Ok(v)) => return Poll::Ready(Ok(v)),
+                Poll::Ready(Err(Closed)) => closed += 1,
+                Poll::Pending => {}
+            }
+        }
+
+        if closed == 2 {
+            Poll::Ready(Err(Closed))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<'b, 'a, 'c, A, B, SA: Storage<A>, SB: Storage<B>> Drop
+    for Select2Future<'b, 'a, 'c, A, B, SA, SB>
+{
+    fn drop(&mut self) {
+        self.a.ssq.rx_waker.clear();
+        self.b.ssq.rx_waker.clear();
+    }
+}
+
+/// Wait for any of `N` consumers to produce a value, and dequeue it along with the index of the
+/// consumer it came from.
+///
+/// As with [`select2`], the consumer checked first moves on to the next one every time the future is
+/// polled, and the future resolves to `Err(Closed)` once all producers have been dropped and all
+/// queues drained.
+#[inline]
+pub fn select_array<'b, 'a, T, const N: usize, S: Storage<T>>(
+    conss: &'b mut [Consumer<'a, T, S>; N],
+) -> SelectArrayFuture<'b, 'a, T, N, S> {
+    SelectArrayFuture { conss, start: 0 }
+}
+
+/// Future returned by [`select_array`].
+///
+/// Dropping the future before it completes deregisters its waker from all queues.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectArrayFuture<'b, 'a, T, const N: usize, S: Storage<T> = Inline<T>> {
+    conss: &'b mut [Consumer<'a, T, S>; N],
+    /// Index of the consumer checked first on the next poll.
+    start: usize,
+}
+
+impl<'b, 'a, T, const N: usize, S: Storage<T>> Future for SelectArrayFuture<'b, 'a, T, N, S> {
+    type Output = Result<(usize, T), Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let start = this.start;
+        this.start = (start + 1) % N;
+        let mut closed = 0;
+        for i in 0..N {
+            let index = (start + i) % N;
+            match this.conss[index].poll_dequeue(cx) {
+                Poll::Ready(Ok(v)) => return Poll::Ready(Ok((index, v))),
+                Poll::Ready(Err(Closed)) => closed += 1,
+                Poll::Pending => {}
+            }
+        }
+
+        if closed == N {
+            Poll::Ready(Err(Closed))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<'b, 'a, T, const N: usize, S: Storage<T>> Drop for SelectArrayFuture<'b, 'a, T, N, S> {
+    fn drop(&mut self) {
+        for cons in self.conss.iter() {
+            cons.ssq.rx_waker.clear();
+        }
+    }
+}
+
+/// Future returned by [`Producer::enqueue_async`].
+///
+/// Resolves once the value has been written into the queue, or to `Err(Disconnected(_))` if the
+/// [`Consumer`] is dropped first. Dropping the future before it completes drops the value, and
+/// deregisters the future's waker from the queue.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct EnqueueFuture<'b, 'a, T, S: Storage<T> = Inline<T>> {
+    pub(crate) prod: &'b mut Producer<'a, T, S>,
+    pub(crate) val: Option<T>,
+}
+
+// The value is never pinned; it is only ever moved into the queue.
+impl<'b, 'a, T, S: Storage<T>> Unpin for EnqueueFuture<'b, 'a, T, S> {}
+
+impl<'b, 'a, T, S: Storage<T>> Future for EnqueueFuture<'b, 'a, T, S> {
+    type Output = Result<(), Disconnected<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        this.prod.poll_enqueue(cx, &mut this.val)
+    }
+}
+
+impl<'b, 'a, T, S: Storage<T>> Drop for EnqueueFuture<'b, 'a, T, S> {
+    fn drop(&mut self) {
+        self.prod.ssq.tx_waker.clear();
+    }
+}
+
+/// What a [`ProducerSink`] does with an item when the queue is already full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum FullBehavior {
+    /// Wait for the consumer to dequeue the pending value before accepting the item.
+    Wait,
+    /// Overwrite the pending value with the item.
+    Overwrite,
+}
+
+/// Async producer handle implementing [`Sink`], created with [`Producer::into_sink`].
+///
+/// Once the [`Consumer`] has been dropped, the sink returns the item that could not be sent in a
+/// [`Disconnected`] error.
+pub struct ProducerSink<'a, T, S: Storage<T> = Inline<T>> {
+    pub(crate) prod: Producer<'a, T, S>,
+    pub(crate) behavior: FullBehavior,
+    pub(crate) pending: Option<T>,
+}
+
+// The pending item is never pinned; it is only ever moved into the queue.
+impl<'a, T, S: Storage<T>> Unpin for ProducerSink<'a, T, S> {}
+
+impl<'a, T, S: Storage<T>> ProducerSink<'a, T, S> {
+    /// Try moving the pending item into the queue. Returns `Ok(true)` if there is no pending
+    /// item left.
+    fn try_push(&mut self) -> Result<bool, Disconnected<T>> {
+        if let Some(val) = self.pending.take() {
+            if !self.prod.is_consumer_connected() {
+                return Err(Disconnected(val));
+            }
+
+            self.pending = match self.behavior {
+                FullBehavior::Wait => self.prod.enqueue(val),
+                // The overwritten item is dropped, rather than sent again.
+                FullBehavior::Overwrite => self
+                    .prod
+                    .try_enqueue_overwrite(val)
+                    .err()
+                    .map(EnqueueError::into_inner),
+            };
+        }
+        Ok(self.pending.is_none())
+    }
+}
+
+impl<'a, T, S: Storage<T>> Sink<T> for ProducerSink<'a, T, S> {
+    type Error = Disconnected<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        debug_assert!(
+            this.pending.is_none(),
+            "`start_send` called before `poll_ready`"
+        );
+        this.pending = Some(item);
+        this.try_push().map(|_| ())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if this.try_push()? {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Woken when the consumer empties the queue, or gives up a slot it was reading.
+        this.prod.ssq.tx_waker.register(cx.waker());
+
+        // Check again, in case the consumer let go of the slot before the waker was registered.
+        if this.try_push()? {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+}
@@ -0,0 +1,42 @@
+//! Instrumentation hook for a [`SingleSlotQueue`](crate::SingleSlotQueue), enabled by the
+//! `hooks` feature, e.g. to feed an RTOS trace tool or toggle a GPIO for a logic analyzer.
+//!
+//! # Example
+//!
+//! ```
+//! use core::sync::atomic::{AtomicUsize, Ordering};
+//! use ssq::{hook::Event, SingleSlotQueue};
+//!
+//! static OVERWRITES: AtomicUsize = AtomicUsize::new(0);
+//!
+//! fn trace(event: Event) {
+//!     if event == Event::Overwrote {
+//!         OVERWRITES.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! queue.set_hook(Some(trace));
+//! let (_cons, mut prod) = queue.split();
+//!
+//! prod.enqueue_overwrite(1);
+//! prod.enqueue_overwrite(2);
+//! assert!(OVERWRITES.load(Ordering::Relaxed) == 1);
+//! ```
+
+/// Event reported to the hook set with
+/// [`SingleSlotQueue::set_hook`](crate::SingleSlotQueue::set_hook).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum Event {
+    /// A value was written into the queue.
+    Enqueued,
+    /// A value was dequeued by the consumer.
+    Dequeued,
+    /// An unread value was displaced by a newer one. Reported right after the
+    /// [`Enqueued`](Event::Enqueued) event of the newer value.
+    Overwrote,
+    /// A value was handed back to the producer because it could not be written.
+    Rejected,
+}
@@ -0,0 +1,68 @@
+//! In-place initialization of values directly in the slot of a
+//! [`SingleSlotQueue`](crate::SingleSlotQueue), in the style of the `pinned-init` crate, so that
+//! very large payloads never have to fit on the stack.
+//!
+//! # Example
+//!
+//! ```
+//! use core::convert::Infallible;
+//! use ssq::{init::init_from_closure, SingleSlotQueue};
+//!
+//! let mut queue = SingleSlotQueue::<[u8; 4096]>::new();
+//! let (mut cons, mut prod) = queue.split();
+//!
+//! // Safety: every byte of the slot is written.
+//! let init = unsafe {
+//!     init_from_closure(|slot: *mut [u8; 4096]| {
+//!         slot.cast::<u8>().write_bytes(0xAA, 4096);
+//!         Ok::<_, Infallible>(())
+//!     })
+//! };
+//! assert!(prod.enqueue_init(init).is_ok());
+//! assert!(cons.peek_with(|frame| frame[4095]) == Some(0xAA));
+//! ```
+
+use core::convert::Infallible;
+
+/// Initializer writing a `T` in place, which may fail with `E`.
+///
+/// Values that can simply be moved into place are better enqueued with
+/// [`Producer::enqueue_with`](crate::Producer::enqueue_with).
+///
+/// # Safety
+///
+/// When [`init`](Init::init) returns `Ok`, the value at `slot` must be fully initialized. When it
+/// returns `Err`, the value at `slot` must be left uninitialized, or at least in a state that does
+/// not need dropping, as it is neither read nor dropped.
+pub unsafe trait Init<T, E = Infallible> {
+    /// Initialize the value at `slot`.
+    ///
+    /// # Safety
+    ///
+    /// `slot` must be valid for writes and properly aligned, and must not hold a value that needs
+    /// dropping, as it is overwritten.
+    unsafe fn init(self, slot: *mut T) -> Result<(), E>;
+}
+
+/// Initializer running a closure against the slot, returned by [`init_from_closure`].
+pub struct InitClosure<F>(F);
+
+unsafe impl<T, E, F: FnOnce(*mut T) -> Result<(), E>> Init<T, E> for InitClosure<F> {
+    #[inline]
+    unsafe fn init(self, slot: *mut T) -> Result<(), E> {
+        (self.0)(slot)
+    }
+}
+
+/// Create an initializer from a closure writing the value through a raw pointer to the slot.
+///
+/// # Safety
+///
+/// The closure must uphold the contract of [`Init`]: the value must be fully initialized if it
+/// returns `Ok`, and must not need dropping if it returns `Err`.
+#[inline]
+pub const unsafe fn init_from_closure<T, E, F: FnOnce(*mut T) -> Result<(), E>>(
+    f: F,
+) -> InitClosure<F> {
+    InitClosure(f)
+}
@@ -0,0 +1,71 @@
+//! Lending iteration over the values published to a [`SingleSlotQueue`](crate::SingleSlotQueue),
+//! enabled by the `lending` feature, for consumers that only inspect values (e.g. a watchdog
+//! monitoring a heartbeat) and never take them out.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{lending::LendingIterator, SingleSlotQueue};
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! let (mut cons, mut prod) = queue.split();
+//! let mut peeks = cons.peeks();
+//!
+//! prod.enqueue_overwrite(1);
+//! assert!(peeks.next().as_deref() == Some(&1));
+//! // The value is still in the queue, but has been seen already.
+//! assert!(peeks.next().is_none());
+//!
+//! prod.enqueue_overwrite(2);
+//! assert!(peeks.next().as_deref() == Some(&2));
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    Consumer, PeekGuard,
+};
+use atomic_polyfill::Ordering;
+
+/// Iterator whose items borrow from the iterator itself, so that only one of them can be held at
+/// a time.
+pub trait LendingIterator {
+    /// Type of the items, borrowing from the iterator for `'i`.
+    type Item<'i>
+    where
+        Self: 'i;
+
+    /// Get the next item, if there is one.
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+/// Lending iterator over the values published to a queue, returned by
+/// [`Consumer::peeks`](crate::Consumer::peeks).
+///
+/// Each call to [`next`](LendingIterator::next) borrows the value in the queue, as with
+/// [`Consumer::peek_ref`](crate::Consumer::peek_ref), if it was published since the last value
+/// returned. Otherwise, it returns `None` without blocking, so iteration can resume once a new
+/// value is published. Values overwritten before they could be borrowed are skipped.
+pub struct Peeks<'c, 'a, T, S: Storage<T> = Inline<T>> {
+    pub(crate) cons: &'c mut Consumer<'a, T, S>,
+    /// Version of the last value returned, if any.
+    pub(crate) seen: Option<usize>,
+}
+
+impl<'c, 'a, T, S: Storage<T>> LendingIterator for Peeks<'c, 'a, T, S> {
+    type Item<'i>
+        = PeekGuard<'i, T, S>
+    where
+        Self: 'i;
+
+    fn next(&mut self) -> Option<PeekGuard<'_, T, S>> {
+        let ssq = self.cons.ssq;
+        let guard = self.cons.peek_ref()?;
+        // The producer cannot publish while the slot is held, so this is the version of the
+        // borrowed value, or an older one if it has not been counted yet.
+        let version = ssq.version.load(Ordering::Acquire);
+        if self.seen == Some(version) {
+            return None;
+        }
+        self.seen = Some(version);
+        Some(guard)
+    }
+}
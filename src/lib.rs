@@ -3,7 +3,7 @@
 //! # Example
 //!
 //! ```
-//! use ssq::{Producer, Consumer, SingleSlotQueue};
+//! use ssq::{Producer, Consumer, Overwrite, SingleSlotQueue};
 //! let mut queue = SingleSlotQueue::<u32>::new();
 //! let (mut cons, mut prod) = queue.split();
 //!
@@ -15,8 +15,10 @@
 //! let maybe_returned = prod.enqueue(2);
 //! assert!(maybe_returned == Some(2));
 //!
-//! // `enqueue_overwrite` overwrites the old value, at the cost of taking a lock.
-//! prod.enqueue_overwrite(25);
+//! // `enqueue_overwrite` overwrites the old value, and returns it. It never blocks, but
+//! // rejects the new value instead if the consumer is in the middle of reading the slot.
+//! let outcome = prod.enqueue_overwrite(25);
+//! assert!(outcome == Overwrite::Overwrote(50));
 //! assert!(cons.dequeue() ==  Some(25));
 //!
 //! // `dequeue` returns `None` if the queue is empty.
@@ -25,168 +27,1967 @@
 //! ```
 
 #![no_std]
+#![cfg_attr(feature = "unstable", feature(async_iterator))]
 
-use atomic_polyfill::{AtomicBool, Ordering};
-use core::{cell::UnsafeCell, mem::MaybeUninit, ptr};
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
-struct LightLock(AtomicBool);
+pub mod adapter;
+pub mod array;
+pub mod bbq;
+#[cfg(feature = "std")]
+pub mod blocking;
+pub mod coalesce;
+pub mod counter;
+pub mod debounce;
+pub mod double;
+pub mod duplex;
+#[cfg(feature = "embassy-time")]
+mod embassy;
+pub mod exchange;
+pub mod fan;
+pub mod flags;
+pub mod frame;
+#[cfg(feature = "async")]
+pub mod future;
+#[cfg(feature = "hooks")]
+pub mod hook;
+pub mod init;
+#[cfg(feature = "lending")]
+pub mod lending;
+pub mod loan;
+pub mod mpmc;
+pub mod mpsc;
+pub mod notify;
+pub mod oneshot;
+pub mod pingpong;
+pub mod pool;
+pub mod priority;
+pub mod ratelimit;
+pub mod ring;
+pub mod rpc;
+#[cfg(feature = "rtic-time")]
+mod rtic;
+mod slot;
+pub mod spmc;
+pub mod spsc;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod sticky;
+pub mod storage;
+pub mod timestamp;
+pub mod triple;
+#[cfg(feature = "async")]
+mod waker;
+pub mod watch;
 
-impl LightLock {
-    const fn new() -> Self {
-        LightLock(AtomicBool::new(false))
+#[cfg(any(feature = "async", feature = "lending"))]
+use atomic_polyfill::AtomicUsize;
+use atomic_polyfill::{AtomicBool, AtomicU8, Ordering};
+#[cfg(feature = "async")]
+use core::task::{Context, Poll};
+use core::{
+    fmt, hint,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+#[cfg(feature = "async")]
+use future::{ChangedFuture, DequeueFuture, EnqueueFuture, FullBehavior, ProducerSink};
+#[cfg(feature = "hooks")]
+use hook::Event;
+use init::Init;
+#[cfg(feature = "lending")]
+use lending::Peeks;
+#[cfg(feature = "contention")]
+use stats::Contention;
+#[cfg(feature = "stats")]
+use stats::{Counters, Stats};
+use storage::{Borrowed, Inline, Storage};
+#[cfg(feature = "async")]
+use waker::WakerCell;
+
+/// The slot holds no value.
+const EMPTY: u8 = 0;
+/// One of the handles has exclusive access to the slot, and is either writing a value in
+/// or reading one out. Because there is only ever one [`Producer`] and one [`Consumer`],
+/// a handle observing this state knows that the *other* handle holds the slot.
+const WRITING: u8 = 1;
+/// The slot holds a value ready to be read.
+const FULL: u8 = 2;
+
+/// State of the slot, as shown when formatting or serializing the queue or its handles.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+enum SlotState {
+    Empty,
+    Busy,
+    Full,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SlotState {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let (index, name) = match self {
+            SlotState::Empty => (0, "Empty"),
+            SlotState::Busy => (1, "Busy"),
+            SlotState::Full => (2, "Full"),
+        };
+        serializer.serialize_unit_variant("SlotState", index, name)
     }
+}
 
-    /// Blocking; busy-wait until the lock is available
-    fn lock(&self) -> LightGuard<'_> {
-        loop {
-            match self.try_lock() {
-                None => continue,
-                Some(w) => return w,
+/// Serialize a snapshot of the slot, made of its state and the value it holds, if any.
+#[cfg(feature = "serde")]
+fn serialize_snapshot<T: serde::Serialize, Ser: serde::Serializer>(
+    serializer: Ser,
+    name: &'static str,
+    state: SlotState,
+    value: Option<&T>,
+) -> Result<Ser::Ok, Ser::Error> {
+    use serde::ser::SerializeStruct;
+
+    let mut s = serializer.serialize_struct(name, 2)?;
+    s.serialize_field("state", &state)?;
+    s.serialize_field("value", &value)?;
+    s.end()
+}
+
+/// Error returned when the [`Producer`] has been dropped and there is no value left in the queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Closed;
+
+/// Error returned when the [`Consumer`] has been dropped, containing the value that could not be
+/// enqueued.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Disconnected<T>(pub T);
+
+impl fmt::Display for Closed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("queue closed")
+    }
+}
+
+impl core::error::Error for Closed {}
+
+impl<T> fmt::Display for Disconnected<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("consumer disconnected")
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for Disconnected<T> {}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Closed {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.write_str("queue closed")
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl<T> ufmt::uDisplay for Disconnected<T> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.write_str("consumer disconnected")
+    }
+}
+
+/// Error returned by [`Producer::try_enqueue`], containing the value that could not be enqueued.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum EnqueueError<T> {
+    /// There is a value already in the queue.
+    Full(T),
+    /// The [`Consumer`] is in the middle of reading the slot.
+    WouldBlock(T),
+    /// The [`Consumer`] has been dropped.
+    Disconnected(T),
+}
+
+impl<T> EnqueueError<T> {
+    /// Take back the value that could not be enqueued.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self {
+            EnqueueError::Full(v) | EnqueueError::WouldBlock(v) | EnqueueError::Disconnected(v) => {
+                v
             }
         }
     }
 
-    fn try_lock(&self) -> Option<LightGuard<'_>> {
-        let was_locked = self.0.swap(true, Ordering::Acquire);
-        if was_locked {
-            None
-        } else {
-            Some(LightGuard { lock: self })
+    /// Apply `f` to the value that could not be enqueued, keeping the reason.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> EnqueueError<U> {
+        match self {
+            EnqueueError::Full(v) => EnqueueError::Full(f(v)),
+            EnqueueError::WouldBlock(v) => EnqueueError::WouldBlock(f(v)),
+            EnqueueError::Disconnected(v) => EnqueueError::Disconnected(f(v)),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            EnqueueError::Full(_) => "queue full",
+            EnqueueError::WouldBlock(_) => "queue busy",
+            EnqueueError::Disconnected(_) => "consumer disconnected",
         }
     }
 }
 
-struct LightGuard<'a> {
-    lock: &'a LightLock,
+/// Error returned by [`Consumer::try_dequeue`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum DequeueError {
+    /// There is no value in the queue.
+    Empty,
+    /// The [`Producer`] is in the middle of writing a value.
+    WouldBlock,
+    /// There is no value in the queue, and the [`Producer`] has been dropped.
+    Closed,
 }
 
-impl<'a> Drop for LightGuard<'a> {
-    fn drop(&mut self) {
-        self.lock.0.store(false, Ordering::Release);
+impl DequeueError {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DequeueError::Empty => "queue empty",
+            DequeueError::WouldBlock => "queue busy",
+            DequeueError::Closed => "queue closed",
+        }
+    }
+}
+
+impl<T> fmt::Display for EnqueueError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for EnqueueError<T> {}
+
+impl fmt::Display for DequeueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl core::error::Error for DequeueError {}
+
+#[cfg(feature = "ufmt")]
+impl<T> ufmt::uDisplay for EnqueueError<T> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for DequeueError {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of [`Producer::enqueue_overwrite`], and of the overwriting writes of the other slot
+/// types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum Overwrite<T> {
+    /// The value was written into an empty queue.
+    Enqueued,
+    /// The value was written, displacing the unread value returned here.
+    Overwrote(T),
+    /// The value could not be written and is handed back, because another handle was in the
+    /// middle of accessing the slot, or the [`Consumer`] has been dropped.
+    Rejected(T),
+}
+
+impl<T> Overwrite<T> {
+    /// Check if the value was written into the queue.
+    #[inline]
+    pub fn is_written(&self) -> bool {
+        !matches!(self, Overwrite::Rejected(_))
+    }
+
+    /// Get the value that did not make it into the queue, if any: either the overwritten value,
+    /// or the rejected one.
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        match self {
+            Overwrite::Enqueued => None,
+            Overwrite::Overwrote(v) | Overwrite::Rejected(v) => Some(v),
+        }
+    }
+
+    /// Map the value that did not make it into the queue, if any.
+    #[inline]
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Overwrite<U> {
+        match self {
+            Overwrite::Enqueued => Overwrite::Enqueued,
+            Overwrite::Overwrote(v) => Overwrite::Overwrote(f(v)),
+            Overwrite::Rejected(v) => Overwrite::Rejected(f(v)),
+        }
     }
 }
 
+/// Outcome of [`Producer::enqueue_lossy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum LossyEnqueue {
+    /// The value was written into an empty queue.
+    Enqueued,
+    /// The value was written, and an unread value was dropped to make room for it.
+    Overwrote,
+    /// The value could not be written and was dropped, because the [`Consumer`] was in the
+    /// middle of reading the slot, or has been dropped.
+    Dropped,
+}
+
 /// Single slot queue.
-pub struct SingleSlotQueue<T> {
-    full: AtomicBool,
-    writing: LightLock,
-    val: UnsafeCell<MaybeUninit<T>>,
+///
+/// The value is held inline by default; see [`from_storage`](SingleSlotQueue::from_storage) to
+/// provide the memory for it instead.
+pub struct SingleSlotQueue<T, S: Storage<T> = Inline<T>> {
+    state: AtomicU8,
+    val: S,
+    _marker: PhantomData<T>,
+    /// Set when the [`Producer`] is dropped.
+    tx_closed: AtomicBool,
+    /// Set when the [`Consumer`] is dropped.
+    rx_closed: AtomicBool,
+    #[cfg(feature = "async")]
+    rx_waker: WakerCell,
+    #[cfg(feature = "async")]
+    tx_waker: WakerCell,
+    /// Incremented every time a value is published.
+    #[cfg(any(feature = "async", feature = "lending"))]
+    version: AtomicUsize,
+    #[cfg(feature = "stats")]
+    stats: Counters,
+    #[cfg(feature = "hooks")]
+    hook: Option<fn(Event)>,
 }
 
 impl<T> SingleSlotQueue<T> {
     pub const fn new() -> Self {
+        Self::with_storage(Inline::new(), EMPTY)
+    }
+
+    /// Create a queue already holding `val`, so that a static queue can start out full (e.g.
+    /// with a default configuration for the consumer to read).
+    #[inline]
+    pub const fn new_with(val: T) -> Self {
+        Self::with_storage(Inline::with_value(val), FULL)
+    }
+}
+
+/// Creates an empty queue.
+impl<T> Default for SingleSlotQueue<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Creates a queue already holding the value, as with [`new_with`](SingleSlotQueue::new_with).
+impl<T> From<T> for SingleSlotQueue<T> {
+    #[inline]
+    fn from(val: T) -> Self {
+        Self::new_with(val)
+    }
+}
+
+impl<'s, T> SingleSlotQueue<T, Borrowed<'s, T>> {
+    /// Create a queue whose value lives in `storage` rather than inline, so it can be placed in a
+    /// specific RAM bank, DMA-capable region, or `.uninit` section.
+    ///
+    /// Any value left in the queue is dropped along with it; `storage` is never read before a
+    /// value has been written to it.
+    pub fn from_storage(storage: &'s mut MaybeUninit<T>) -> Self {
+        Self::with_storage(Borrowed::new(storage), EMPTY)
+    }
+}
+
+impl<T, S: Storage<T>> SingleSlotQueue<T, S> {
+    /// Create a queue in `state`, which must be [`FULL`] only if `val` holds a value.
+    const fn with_storage(val: S, state: u8) -> Self {
         SingleSlotQueue {
-            full: AtomicBool::new(false),
-            writing: LightLock::new(),
-            val: UnsafeCell::new(MaybeUninit::uninit()),
+            state: AtomicU8::new(state),
+            val,
+            _marker: PhantomData,
+            tx_closed: AtomicBool::new(false),
+            rx_closed: AtomicBool::new(false),
+            #[cfg(feature = "async")]
+            rx_waker: WakerCell::new(),
+            #[cfg(feature = "async")]
+            tx_waker: WakerCell::new(),
+            #[cfg(any(feature = "async", feature = "lending"))]
+            version: AtomicUsize::new(0),
+            #[cfg(feature = "stats")]
+            stats: Counters::new(),
+            #[cfg(feature = "hooks")]
+            hook: None,
+        }
+    }
+
+    /// Set the function called on every queue [`Event`], or remove it with `None`.
+    ///
+    /// The hook is called from whichever context triggered the event (possibly an interrupt
+    /// handler), after the slot has been released, so it should be kept short.
+    #[cfg(feature = "hooks")]
+    #[inline]
+    pub fn set_hook(&mut self, hook: Option<fn(Event)>) {
+        self.hook = hook;
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T, S>, Producer<'_, T, S>) {
+        // Handles from a previous split are gone, so the queue can be reopened.
+        *self.tx_closed.get_mut() = false;
+        *self.rx_closed.get_mut() = false;
+        let cons = Consumer {
+            #[cfg(feature = "async")]
+            seen: *self.version.get_mut(),
+            ssq: self,
+        };
+        (cons, Producer { ssq: self })
+    }
+
+    /// Borrow the value in the queue mutably, if any.
+    ///
+    /// This needs exclusive access to the queue, so it can only be used while there are no
+    /// handles, e.g. in setup code before [`split`](SingleSlotQueue::split).
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        if *self.state.get_mut() == FULL {
+            Some(unsafe { &mut *self.val.as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Consume the queue, returning the value left in it, if any.
+    ///
+    /// The handles borrow the queue, so they must have been dropped already.
+    #[inline]
+    pub fn into_inner(mut self) -> Option<T> {
+        let state = self.state.get_mut();
+        if *state == FULL {
+            // The queue is dropped as if it were empty, so the value is only read once.
+            *state = EMPTY;
+            Some(unsafe { ptr::read(self.val.as_ptr()) })
+        } else {
+            None
+        }
+    }
+
+    /// Try to take exclusive access to the slot by moving it from `from` to [`WRITING`].
+    #[inline]
+    fn acquire(&self, from: u8) -> bool {
+        self.try_acquire(from).is_ok()
+    }
+
+    /// Like [`acquire`](Self::acquire), but returns the state the slot was in on failure.
+    #[inline]
+    fn try_acquire(&self, from: u8) -> Result<(), u8> {
+        match self
+            .state
+            .compare_exchange(from, WRITING, Ordering::Acquire, Ordering::Relaxed)
+        {
+            Ok(_) => Ok(()),
+            Err(actual) => {
+                #[cfg(feature = "contention")]
+                if actual == WRITING {
+                    self.stats.busy();
+                }
+                Err(actual)
+            }
+        }
+    }
+
+    /// Try to take exclusive access to the slot from the producer side, whether it is empty or
+    /// full. Returns the state the slot was in, or `None` if the consumer is accessing it.
+    #[inline]
+    fn acquire_any(&self) -> Option<u8> {
+        // The consumer can only ever move the slot from FULL to WRITING, so this loop runs at
+        // most twice: once the slot is observed as WRITING, we give up instead of spinning.
+        let mut current = self.state.load(Ordering::Relaxed);
+        loop {
+            if current == WRITING {
+                #[cfg(feature = "contention")]
+                self.stats.busy();
+                return None;
+            }
+
+            match self.state.compare_exchange(
+                current,
+                WRITING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(prev) => return Some(prev),
+                Err(actual) => {
+                    #[cfg(feature = "contention")]
+                    self.stats.retried();
+                    current = actual;
+                }
+            }
+        }
+    }
+
+    /// Give up exclusive access to the slot, leaving it in state `to`.
+    #[inline]
+    fn release(&self, to: u8) {
+        self.state.store(to, Ordering::Release);
+    }
+
+    /// Publish a freshly written value, waking the consumer if it is waiting for one.
+    #[inline]
+    fn publish(&self) {
+        self.release(FULL);
+        #[cfg(feature = "stats")]
+        self.stats.enqueued();
+        #[cfg(feature = "hooks")]
+        self.emit(Event::Enqueued);
+        #[cfg(any(feature = "async", feature = "lending"))]
+        self.version.fetch_add(1, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.rx_waker.wake();
+    }
+
+    /// Give back a full slot taken by the consumer without taking its value out, waking the
+    /// producer in case it gave up on overwriting the value in the meantime.
+    #[inline]
+    fn unlock(&self) {
+        self.release(FULL);
+        #[cfg(feature = "async")]
+        self.tx_waker.wake();
+    }
+
+    /// Mark a value as taken out of the slot, waking the producer if it is waiting for room.
+    #[inline]
+    fn free(&self) {
+        self.release(EMPTY);
+        #[cfg(feature = "async")]
+        self.tx_waker.wake();
+    }
+
+    /// Record a value dequeued by the consumer.
+    #[inline]
+    fn dequeued(&self) {
+        #[cfg(feature = "stats")]
+        self.stats.dequeued();
+        #[cfg(feature = "hooks")]
+        self.emit(Event::Dequeued);
+    }
+
+    /// Record an unread value displaced by a newer one.
+    #[inline]
+    fn overwrote(&self) {
+        #[cfg(feature = "log")]
+        log::debug!("overwrote an unread value");
+        #[cfg(feature = "stats")]
+        self.stats.overwrote();
+        #[cfg(feature = "hooks")]
+        self.emit(Event::Overwrote);
+    }
+
+    /// Record a value handed back to the producer because it could not be written.
+    #[inline]
+    fn rejected(&self) {
+        #[cfg(feature = "log")]
+        log::debug!("rejected an enqueued value");
+        #[cfg(feature = "stats")]
+        self.stats.rejected();
+        #[cfg(feature = "hooks")]
+        self.emit(Event::Rejected);
+    }
+
+    #[cfg(feature = "hooks")]
+    #[inline]
+    fn emit(&self, event: Event) {
+        if let Some(hook) = self.hook {
+            hook(event);
+        }
+    }
+
+    /// Current state of the slot, for diagnostics.
+    fn slot_state(&self) -> SlotState {
+        match self.state.load(Ordering::Relaxed) {
+            EMPTY => SlotState::Empty,
+            FULL => SlotState::Full,
+            _ => SlotState::Busy,
+        }
+    }
+
+    /// Run `f` against the value in the slot without taking it out, if there is one and the other
+    /// handle is not accessing the slot.
+    #[inline]
+    fn inspect<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.try_inspect(f).ok()
+    }
+
+    /// Like [`inspect`](Self::inspect), but returns the state the slot was in on failure.
+    #[inline]
+    fn try_inspect<R>(&self, f: impl FnOnce(&T) -> R) -> Result<R, u8> {
+        self.try_acquire(FULL)?;
+        // Gives the slot back even if `f` panics.
+        let guard = PeekGuard { ssq: self };
+        Ok(f(&guard))
+    }
+
+    /// Add the state of the slot to `d`, along with the value it holds, if it can be read.
+    fn debug_slot(&self, d: &mut fmt::DebugStruct<'_, '_>)
+    where
+        T: fmt::Debug,
+    {
+        let shown = self.inspect(|v| {
+            d.field("state", &SlotState::Full).field("value", v);
+        });
+        if shown.is_none() {
+            d.field("state", &self.slot_state());
+        }
+    }
+
+    /// Take a published value back out of the slot, from the producer side.
+    #[inline]
+    fn reclaim(&self) -> Option<T> {
+        if self.acquire(FULL) {
+            let r = unsafe { ptr::read(self.val.as_ptr()) };
+            self.release(EMPTY);
+            Some(r)
+        } else {
+            None
         }
     }
+}
+
+/// Shows the state of the slot, and the value it holds, if any.
+impl<T: fmt::Debug, S: Storage<T>> fmt::Debug for SingleSlotQueue<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("SingleSlotQueue");
+        self.debug_slot(&mut d);
+        d.finish()
+    }
+}
+
+/// Formats the state of the slot, but not the value it may hold.
+#[cfg(feature = "defmt")]
+impl<T, S: Storage<T>> defmt::Format for SingleSlotQueue<T, S> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(f, "SingleSlotQueue {{ state: {} }}", self.slot_state());
+    }
+}
+
+/// Formats the state of the slot, but not the value it may hold.
+#[cfg(feature = "ufmt")]
+impl<T, S: Storage<T>> ufmt::uDebug for SingleSlotQueue<T, S> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.debug_struct("SingleSlotQueue")?
+            .field("state", &self.slot_state())?
+            .finish()
+    }
+}
 
-    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
-        (Consumer { ssq: self }, Producer { ssq: self })
+/// Serializes a snapshot of the queue: the state of the slot, and the value it holds, if any.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize, S: Storage<T>> serde::Serialize for SingleSlotQueue<T, S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let state = self.slot_state();
+        // Safety: the handles borrow the queue mutably, so none of them can be accessing the
+        // slot while we hold a shared reference to it.
+        let value = match state {
+            SlotState::Full => Some(unsafe { &*self.val.as_ptr() }),
+            _ => None,
+        };
+        serialize_snapshot(serializer, "SingleSlotQueue", state, value)
     }
 }
 
-impl<T> Drop for SingleSlotQueue<T> {
+impl<T, S: Storage<T>> Drop for SingleSlotQueue<T, S> {
     fn drop(&mut self) {
-        if self.full.load(Ordering::Relaxed) {
+        if *self.state.get_mut() == FULL {
             unsafe {
-                ptr::drop_in_place(self.val.get() as *mut T);
+                ptr::drop_in_place(self.val.as_ptr());
             }
         }
     }
 }
 
 /// Read handle to a single slot queue.
-pub struct Consumer<'a, T> {
-    ssq: &'a SingleSlotQueue<T>,
+///
+/// The handles can only be sent to another thread if `T` is [`Send`]:
+///
+/// ```compile_fail
+/// use std::{rc::Rc, thread};
+/// let mut queue = ssq::SingleSlotQueue::<Rc<u32>>::new();
+/// let (cons, _prod) = queue.split();
+/// thread::scope(|scope| {
+///     scope.spawn(move || drop(cons));
+/// });
+/// ```
+pub struct Consumer<'a, T, S: Storage<T> = Inline<T>> {
+    ssq: &'a SingleSlotQueue<T, S>,
+    /// Last version of the queue seen by [`changed`](Consumer::changed).
+    #[cfg(feature = "async")]
+    seen: usize,
 }
 
-impl<'a, T> Consumer<'a, T> {
+impl<'a, T, S: Storage<T>> Consumer<'a, T, S> {
     /// Try reading a value from the queue.
     ///
-    /// # Blocking
-    ///
-    /// This method blocks if the corresponding [`Producer`] is currently [`enqueue_overwrite`](Producer::enqueue_overwrite)ing
+    /// This method never blocks. If the corresponding [`Producer`] is in the middle of
+    /// writing a value, that value is not published yet and `None` is returned.
     #[inline]
     pub fn dequeue(&mut self) -> Option<T> {
-        if self.ssq.full.load(Ordering::Acquire) {
-            // SAFETY: locking and holding onto the guard is important for enqueue_overwrite to be sound.
-            let _guard = self.ssq.writing.lock();
-            let r = Some(unsafe { ptr::read(self.ssq.val.get().cast()) });
-            self.ssq.full.store(false, Ordering::Release);
-            r
+        self.take().ok()
+    }
+
+    /// Dequeue the value by running `f` against it in place, then dropping it, and return the
+    /// result of `f`.
+    ///
+    /// This avoids moving large payloads out of the queue through the return value of
+    /// [`dequeue`](Consumer::dequeue). The slot is freed once `f` returns. This method never
+    /// blocks: if the corresponding [`Producer`] is in the middle of writing a value, `f` is not
+    /// called and `None` is returned.
+    ///
+    /// If `f` panics, the value is still dropped, and the slot freed.
+    #[inline]
+    pub fn dequeue_with<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        if self.ssq.acquire(FULL) {
+            // Drops the value and frees the slot once `f` returns, or if it panics.
+            let _grant = ReadGrant { ssq: self.ssq };
+            Some(f(unsafe { &mut *self.ssq.val.as_ptr() }))
         } else {
             None
         }
     }
 
-    /// Check if there is a value in the queue.
+    /// Drop the value in the queue, if any, without returning it, and return `true` if there was
+    /// one.
+    ///
+    /// This never blocks: if the corresponding [`Producer`] is in the middle of writing a value,
+    /// that value is not published yet, is left in the queue, and `false` is returned.
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        !self.ssq.full.load(Ordering::Relaxed)
+    pub fn clear(&mut self) -> bool {
+        self.dequeue_with(|_| ()).is_some()
     }
-}
 
-impl<'a, T: Copy> Consumer<'a, T> {
-    /// Try reading a value without dequeuing.
+    /// Get exclusive access to the value in the queue, and a pointer to it, so that a DMA
+    /// peripheral can read it straight from the slot. The slot is then handed back to the
+    /// producer with [`mark_read`](Consumer::mark_read).
     ///
-    /// # Blocking
+    /// Returns `None` if the queue is empty, or the corresponding [`Producer`] is in the middle
+    /// of writing a value. The queue stays busy until the value is marked as read.
     ///
-    /// This method blocks if the corresponding [`Producer`] is currently [`enqueue_overwrite`](Producer::enqueue_overwrite)ing
-    pub fn peek(&mut self) -> Option<T> {
-        if self.ssq.full.load(Ordering::Acquire) {
-            // SAFETY: locking and holding onto the guard is important for enqueue_overwrite to be sound.
-            let _guard = self.ssq.writing.lock();
-            Some(unsafe { ptr::read(self.ssq.val.get().cast()) })
+    /// # Safety
+    ///
+    /// [`mark_read`](Consumer::mark_read) must eventually be called, or the queue is left
+    /// permanently busy.
+    #[inline]
+    pub unsafe fn read_ptr(&mut self) -> Option<*const T> {
+        if self.ssq.acquire(FULL) {
+            Some(self.ssq.val.as_ptr())
         } else {
             None
         }
     }
-}
 
-/// Safety: We gurarantee the safety using an `AtomicBool` to gate the read of the `UnsafeCell`.
-unsafe impl<'a, T> Send for Consumer<'a, T> {}
+    /// Hand the slot taken with [`read_ptr`](Consumer::read_ptr) back to the producer.
+    ///
+    /// The value is treated as moved out of the queue, and is not dropped.
+    ///
+    /// # Safety
+    ///
+    /// The slot must have been taken with `read_ptr` and not marked as read since, and the reads
+    /// from it must be complete (e.g. the DMA transfer must be done). If `T` needs dropping, the
+    /// caller is responsible for it. The former is checked with a debug assertion.
+    #[inline]
+    pub unsafe fn mark_read(&mut self) {
+        debug_assert!(self.ssq.state.load(Ordering::Relaxed) == WRITING);
+        self.ssq.free();
+        self.ssq.dequeued();
+    }
+
+    /// Get exclusive access to the value in the queue, to process it in place; the value is
+    /// dropped and the slot freed once the grant is [`release`](ReadGrant::release)d or dropped.
+    ///
+    /// Unlike [`peek_ref`](Consumer::peek_ref), this consumes the value. This never blocks: if
+    /// the corresponding [`Producer`] is in the middle of writing a value, `None` is returned.
+    ///
+    /// If the grant is leaked, the queue is left permanently busy.
+    #[inline]
+    pub fn read_grant(&mut self) -> Option<ReadGrant<'_, T, S>> {
+        if self.ssq.acquire(FULL) {
+            Some(ReadGrant { ssq: self.ssq })
+        } else {
+            None
+        }
+    }
 
-/// Write handle to a single slot queue.
-pub struct Producer<'a, T> {
-    ssq: &'a SingleSlotQueue<T>,
-}
+    /// Dequeue the value in the queue, leaving `val` in its place, so the slot is never seen
+    /// empty (e.g. when swapping buffers with the producer).
+    ///
+    /// `val` is not published as a new value: the consumer is not woken for it, and it does not
+    /// count as an enqueue. This method never blocks: if the queue is empty, or the corresponding
+    /// [`Producer`] is in the middle of writing a value, `val` is handed back in `Err` instead.
+    #[inline]
+    pub fn dequeue_replace(&mut self, val: T) -> Result<T, T> {
+        if self.ssq.acquire(FULL) {
+            let r = unsafe { ptr::replace(self.ssq.val.as_ptr(), val) };
+            self.ssq.unlock();
+            self.ssq.dequeued();
+            Ok(r)
+        } else {
+            Err(val)
+        }
+    }
 
-impl<'a, T> Producer<'a, T> {
-    /// Write a value into the queue. If there is a value already in the queue this will
-    /// return the value given to this method.
+    /// Dequeue the value in the queue only if `pred` returns `true` for it, leaving it in the
+    /// queue otherwise.
+    ///
+    /// The corresponding [`Producer`] cannot overwrite the value while `pred` runs. This method
+    /// never blocks: if the producer is in the middle of writing a value, `pred` is not called
+    /// and `None` is returned.
     #[inline]
-    pub fn enqueue(&mut self, val: T) -> Option<T> {
-        if !self.ssq.full.load(Ordering::Acquire) {
-            unsafe { ptr::write(self.ssq.val.get().cast(), val) };
-            self.ssq.full.store(true, Ordering::Release);
+    pub fn take_if(&mut self, pred: impl FnOnce(&T) -> bool) -> Option<T> {
+        if !self.ssq.acquire(FULL) {
+            return None;
+        }
+
+        // Gives the slot back, with the value left in it, if `pred` returns `false` or panics.
+        let guard = PeekGuard { ssq: self.ssq };
+        if pred(&guard) {
+            mem::forget(guard);
+            let r = unsafe { ptr::read(self.ssq.val.as_ptr()) };
+            self.ssq.free();
+            self.ssq.dequeued();
+            Some(r)
+        } else {
             None
+        }
+    }
+
+    /// Dequeue the value in the queue without checking that there is one.
+    ///
+    /// This skips the atomic compare-and-swap of [`dequeue`](Consumer::dequeue), for hot paths
+    /// (e.g. an interrupt handler) where the caller already knows the queue is full.
+    ///
+    /// # Safety
+    ///
+    /// The queue must hold a value, for instance because [`is_empty`](Consumer::is_empty)
+    /// returned `false`, and the corresponding [`Producer`] must not access the slot until this
+    /// returns. The producer only ever accesses a full slot to overwrite or take back its value
+    /// (e.g. with [`enqueue_overwrite`](Producer::enqueue_overwrite)), so this holds if it only
+    /// uses [`enqueue`](Producer::enqueue) and its variants. This is checked with a debug
+    /// assertion.
+    #[inline]
+    pub unsafe fn dequeue_unchecked(&mut self) -> T {
+        debug_assert!(self.ssq.state.load(Ordering::Relaxed) == FULL);
+        // Make the value written by the producer visible, as the compare-and-swap would.
+        self.ssq.state.load(Ordering::Acquire);
+        let r = ptr::read(self.ssq.val.as_ptr());
+        self.ssq.free();
+        self.ssq.dequeued();
+        r
+    }
+
+    /// Try reading a value from the queue, reporting why none could be read.
+    ///
+    /// Like [`dequeue`](Consumer::dequeue), this method never blocks. Returns
+    /// `Err(DequeueError::WouldBlock)` if the corresponding [`Producer`] is in the middle of
+    /// writing a value, and `Err(DequeueError::Closed)` instead of `Err(DequeueError::Empty)` once
+    /// the producer has been dropped.
+    ///
+    /// `WouldBlock` is returned as soon as the producer is seen holding the slot, without waiting
+    /// for it to finish, so a consumer running at a lower priority than the producer can yield
+    /// and retry later instead of spinning.
+    #[inline]
+    pub fn try_dequeue(&mut self) -> Result<T, DequeueError> {
+        match self.take() {
+            Ok(v) => Ok(v),
+            Err(WRITING) => Err(DequeueError::WouldBlock),
+            // The producer may have published a final value right before closing.
+            Err(_) if self.is_closed() => self.dequeue().ok_or(DequeueError::Closed),
+            Err(_) => Err(DequeueError::Empty),
+        }
+    }
+
+    /// Try reading a value from the queue, for use in `nb`-style polling drivers and with
+    /// [`nb::block!`].
+    ///
+    /// Returns `Err(nb::Error::WouldBlock)` if there is no value to read yet, and
+    /// `Err(nb::Error::Other(Closed))` once the corresponding [`Producer`] has been dropped and
+    /// the queue is empty.
+    #[cfg(feature = "nb")]
+    #[inline]
+    pub fn dequeue_nb(&mut self) -> nb::Result<T, Closed> {
+        match self.try_dequeue() {
+            Ok(v) => Ok(v),
+            Err(DequeueError::Closed) => Err(nb::Error::Other(Closed)),
+            Err(_) => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    /// Take the value out of the slot, or return the state the slot was in.
+    #[inline]
+    fn take(&mut self) -> Result<T, u8> {
+        self.ssq.try_acquire(FULL)?;
+        let r = unsafe { ptr::read(self.ssq.val.as_ptr()) };
+        self.ssq.free();
+        self.ssq.dequeued();
+        Ok(r)
+    }
+
+    /// Run `f` against the value in the queue, without dequeuing, and return its result.
+    ///
+    /// Unlike [`peek`](Consumer::peek), this does not require `T: Copy`, so payloads such as
+    /// buffers can be inspected in place. The corresponding [`Producer`] cannot overwrite the
+    /// value while `f` runs, and gives up instead, so `f` should be kept short. This method never
+    /// blocks: if the producer is in the middle of writing a value, `f` is not called and `None`
+    /// is returned.
+    #[inline]
+    pub fn peek_with<R>(&mut self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.ssq.inspect(f)
+    }
+
+    /// Run `f` against the value in the queue, letting it modify the value in place without
+    /// dequeuing, and return its result.
+    ///
+    /// This patches a pending value (e.g. decrementing a TTL) without taking it out and
+    /// enqueuing it again, which would race with the corresponding [`Producer`]. As with
+    /// [`peek_with`](Consumer::peek_with), the producer cannot overwrite the value while `f`
+    /// runs, and this method never blocks: if the producer is in the middle of writing a value,
+    /// `f` is not called and `None` is returned.
+    ///
+    /// If `f` panics, the value is left in the queue as `f` left it.
+    #[inline]
+    pub fn peek_mut<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        if self.ssq.acquire(FULL) {
+            // Gives the slot back even if `f` panics.
+            let _guard = PeekGuard { ssq: self.ssq };
+            Some(f(unsafe { &mut *self.ssq.val.as_ptr() }))
         } else {
-            Some(val)
+            None
         }
     }
 
-    /// Write a value into the queue, overwriting the old value if it exists.
+    /// Borrow the value in the queue, without dequeuing.
     ///
-    /// # Blocking
+    /// The returned guard dereferences to the value, which is neither copied nor moved. The
+    /// corresponding [`Producer`] cannot overwrite the value until the guard is dropped, and
+    /// gives up instead, so the guard should not be held for long. This method never blocks: if
+    /// the producer is in the middle of writing a value, `None` is returned.
     ///
-    /// This method blocks if the corresponding [`Consumer`] is currently [`dequeue`](Consumer::dequeue)ing.
-    pub fn enqueue_overwrite(&mut self, val: T) {
-        // SAFETY: locking and holding onto the guard is important
-        let _guard = self.ssq.writing.lock();
-        self.ssq.full.store(false, Ordering::Release);
-        unsafe { ptr::write(self.ssq.val.get().cast(), val) };
-        self.ssq.full.store(true, Ordering::Release);
+    /// If the guard is leaked, the queue is left permanently busy.
+    #[inline]
+    pub fn peek_ref(&mut self) -> Option<PeekGuard<'_, T, S>> {
+        if self.ssq.acquire(FULL) {
+            Some(PeekGuard { ssq: self.ssq })
+        } else {
+            None
+        }
     }
 
-    /// Check if there is a value in the queue.
+    /// Iterator dequeuing values until the queue is observed empty, e.g. to handle every pending
+    /// message from a main loop with `for msg in cons.drain()`.
+    ///
+    /// The iterator never blocks. Once it has returned `None`, it may return values again if the
+    /// corresponding [`Producer`] has enqueued more since.
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        !self.ssq.full.load(Ordering::Relaxed)
+    pub fn drain(&mut self) -> Drain<'_, 'a, T, S> {
+        Drain { cons: self }
+    }
+
+    /// Lending iterator borrowing each new value published to the queue, without dequeuing it.
+    ///
+    /// The value in the queue when this is called, if any, counts as new.
+    #[cfg(feature = "lending")]
+    #[inline]
+    pub fn peeks(&mut self) -> Peeks<'_, 'a, T, S> {
+        Peeks {
+            cons: self,
+            seen: None,
+        }
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ssq.state.load(Ordering::Relaxed) != FULL
+    }
+
+    /// Check if there is a value in the queue, the opposite of
+    /// [`is_empty`](Self::is_empty).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.ssq.state.load(Ordering::Relaxed) == FULL
+    }
+
+    /// Check if the corresponding [`Producer`] has been dropped.
+    ///
+    /// The producer may have enqueued a final value before being dropped, which can still be
+    /// [`dequeue`](Consumer::dequeue)d.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.ssq.tx_closed.load(Ordering::Acquire)
+    }
+
+    /// Wait for a value to be enqueued, and dequeue it.
+    ///
+    /// The returned future registers the task's waker with the queue, and is woken by the
+    /// corresponding [`Producer`] when it enqueues a value. Once the producer has been dropped
+    /// and its final value (if any) dequeued, the future resolves to `Err(Closed)`.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn dequeue_async(&mut self) -> DequeueFuture<'_, 'a, T, S> {
+        DequeueFuture { cons: self }
+    }
+
+    /// Poll for a value, for use in hand-written futures.
+    ///
+    /// Returns `Poll::Ready(Ok(_))` with the dequeued value if there is one, or
+    /// `Poll::Ready(Err(Closed))` if the queue is empty and the corresponding [`Producer`] has
+    /// been dropped. Otherwise, registers the task's waker to be woken when the producer
+    /// enqueues a value or is dropped, and returns `Poll::Pending`. Only the waker from the most
+    /// recent call is woken.
+    #[cfg(feature = "async")]
+    pub fn poll_dequeue(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, Closed>> {
+        if let Some(v) = self.dequeue() {
+            return Poll::Ready(Ok(v));
+        }
+
+        self.ssq.rx_waker.register(cx.waker());
+
+        // Check again, in case the producer enqueued a value before the waker was registered.
+        if let Some(v) = self.dequeue() {
+            return Poll::Ready(Ok(v));
+        }
+
+        if self.is_closed() {
+            // The producer may have published a final value right before closing.
+            return Poll::Ready(self.dequeue().ok_or(Closed));
+        }
+
+        Poll::Pending
+    }
+
+    /// Wait for a new value to be published.
+    ///
+    /// The returned future resolves as soon as the corresponding [`Producer`] has published a
+    /// value since the last time a future returned by this method resolved (or since the queue
+    /// was split), even if that value has since been overwritten, or already dequeued. This is
+    /// meant for "latest value" consumers, which can then [`peek`](Consumer::peek) or
+    /// [`dequeue`](Consumer::dequeue) the current value. Once the producer has been dropped
+    /// and no new value was published, the future resolves to `Err(Closed)`.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn changed(&mut self) -> ChangedFuture<'_, 'a, T, S> {
+        ChangedFuture { cons: self }
+    }
+
+    /// Snapshot of the queue's traffic counters.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.ssq.stats.snapshot()
+    }
+
+    /// Snapshot of the queue's contention counters.
+    #[cfg(feature = "contention")]
+    #[inline]
+    pub fn contention(&self) -> Contention {
+        self.ssq.stats.contention()
+    }
+}
+
+impl<'a, T: Copy, S: Storage<T>> Consumer<'a, T, S> {
+    /// Try reading a value without dequeuing.
+    ///
+    /// This method never blocks. If the corresponding [`Producer`] is in the middle of
+    /// writing a value, that value is not published yet and `None` is returned.
+    pub fn peek(&mut self) -> Option<T> {
+        self.peek_with(|v| *v)
+    }
+
+    /// Try reading a value without dequeuing, reporting why none could be read.
+    ///
+    /// Like [`peek`](Consumer::peek), this method never blocks. As with
+    /// [`try_dequeue`](Consumer::try_dequeue), `Err(DequeueError::WouldBlock)` is returned as soon
+    /// as the corresponding [`Producer`] is seen writing a value, so the latest value can be
+    /// sampled opportunistically.
+    pub fn try_peek(&mut self) -> Result<T, DequeueError> {
+        match self.ssq.try_inspect(|v| *v) {
+            Ok(v) => Ok(v),
+            Err(WRITING) => Err(DequeueError::WouldBlock),
+            // The producer may have published a final value right before closing.
+            Err(_) if self.is_closed() => self.peek().ok_or(DequeueError::Closed),
+            Err(_) => Err(DequeueError::Empty),
+        }
+    }
+}
+
+impl<'a, T: Clone, S: Storage<T>> Consumer<'a, T, S> {
+    /// Try reading a clone of the value, without dequeuing.
+    ///
+    /// This method never blocks. If the corresponding [`Producer`] is in the middle of
+    /// writing a value, that value is not published yet and `None` is returned. The producer
+    /// cannot overwrite the value while it is being cloned, so this is best suited to payloads
+    /// that are cheap to clone.
+    pub fn peek_cloned(&mut self) -> Option<T> {
+        self.peek_with(T::clone)
+    }
+}
+
+/// Shows the state of the slot, and the value it holds if the producer is not in the middle of
+/// writing it.
+impl<'a, T: fmt::Debug, S: Storage<T>> fmt::Debug for Consumer<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("Consumer");
+        self.ssq.debug_slot(&mut d);
+        d.field("closed", &self.is_closed()).finish()
+    }
+}
+
+/// Formats the state of the slot, but not the value it may hold.
+#[cfg(feature = "defmt")]
+impl<'a, T, S: Storage<T>> defmt::Format for Consumer<'a, T, S> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "Consumer {{ state: {}, closed: {=bool} }}",
+            self.ssq.slot_state(),
+            self.is_closed()
+        );
+    }
+}
+
+/// Formats the state of the slot, but not the value it may hold.
+#[cfg(feature = "ufmt")]
+impl<'a, T, S: Storage<T>> ufmt::uDebug for Consumer<'a, T, S> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.debug_struct("Consumer")?
+            .field("state", &self.ssq.slot_state())?
+            .field("closed", &self.is_closed())?
+            .finish()
+    }
+}
+
+/// Serializes a snapshot of the queue, as with the [`SingleSlotQueue`] implementation.
+///
+/// The value is read in place, without dequeuing it, although the producer cannot overwrite it
+/// while it is being serialized. If the producer is in the middle of writing a value, the state
+/// is serialized as `Busy`, without a value.
+#[cfg(feature = "serde")]
+impl<'a, T: serde::Serialize, S: Storage<T>> serde::Serialize for Consumer<'a, T, S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        if self.ssq.acquire(FULL) {
+            // Gives the slot back even if serializing the value panics.
+            let guard = PeekGuard { ssq: self.ssq };
+            serialize_snapshot(serializer, "Consumer", SlotState::Full, Some(&*guard))
+        } else {
+            serialize_snapshot::<T, _>(serializer, "Consumer", self.ssq.slot_state(), None)
+        }
+    }
+}
+
+impl<'a, T, S: Storage<T>> Drop for Consumer<'a, T, S> {
+    fn drop(&mut self) {
+        self.ssq.rx_closed.store(true, Ordering::Release);
+        #[cfg(feature = "log")]
+        log::debug!("consumer dropped");
+        #[cfg(feature = "async")]
+        self.ssq.tx_waker.wake();
+    }
+}
+
+/// Safety: We gurarantee the safety using an `AtomicU8` to gate the read of the `UnsafeCell`.
+/// The value is handed over from another thread, and the storage is shared with the producer.
+unsafe impl<'a, T: Send, S: Storage<T> + Sync> Send for Consumer<'a, T, S> {}
+
+/// Borrow of the value in a queue, returned by [`Consumer::peek_ref`].
+///
+/// The slot is held until the guard is dropped, at which point the value is left in the queue.
+pub struct PeekGuard<'c, T, S: Storage<T> = Inline<T>> {
+    ssq: &'c SingleSlotQueue<T, S>,
+}
+
+impl<'c, T, S: Storage<T>> Deref for PeekGuard<'c, T, S> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: the slot was acquired while full, and is held until the guard is dropped.
+        unsafe { &*self.ssq.val.as_ptr() }
+    }
+}
+
+impl<'c, T: fmt::Debug, S: Storage<T>> fmt::Debug for PeekGuard<'c, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<'c, T, S: Storage<T>> Drop for PeekGuard<'c, T, S> {
+    fn drop(&mut self) {
+        self.ssq.unlock();
+    }
+}
+
+/// Exclusive access to the value in a queue, returned by [`Consumer::read_grant`].
+///
+/// The grant dereferences to the value. The value is dropped, and the slot handed back to the
+/// producer, when the grant is [`release`](ReadGrant::release)d or dropped.
+pub struct ReadGrant<'c, T, S: Storage<T> = Inline<T>> {
+    ssq: &'c SingleSlotQueue<T, S>,
+}
+
+impl<'c, T, S: Storage<T>> ReadGrant<'c, T, S> {
+    /// Drop the value, and hand the slot back to the producer. This is the same as dropping the
+    /// grant.
+    #[inline]
+    pub fn release(self) {}
+}
+
+impl<'c, T, S: Storage<T>> Deref for ReadGrant<'c, T, S> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: the slot was acquired while full, and is held until the grant is dropped.
+        unsafe { &*self.ssq.val.as_ptr() }
+    }
+}
+
+/// Safety: The value stays in the slot when the grant is moved, and until it is dropped.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<'c, T: embedded_dma::ReadTarget, S: Storage<T>> embedded_dma::ReadBuffer
+    for ReadGrant<'c, T, S>
+{
+    type Word = T::Word;
+
+    #[inline]
+    unsafe fn read_buffer(&self) -> (*const T::Word, usize) {
+        embedded_dma::ReadTarget::as_read_buffer(&**self)
+    }
+}
+
+impl<'c, T, S: Storage<T>> Drop for ReadGrant<'c, T, S> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.ssq.val.as_ptr()) };
+        self.ssq.free();
+        self.ssq.dequeued();
+    }
+}
+
+/// Exclusive access to the empty slot of a queue, returned by [`Producer::write_grant`].
+///
+/// The grant dereferences to the slot, as a possibly uninitialized value. Dropping the grant
+/// without [`commit`](WriteGrant::commit)ting it leaves the queue empty.
+pub struct WriteGrant<'p, T, S: Storage<T> = Inline<T>> {
+    ssq: &'p SingleSlotQueue<T, S>,
+}
+
+impl<'p, T, S: Storage<T>> WriteGrant<'p, T, S> {
+    /// Publish the value built in the slot to the consumer.
+    ///
+    /// # Safety
+    ///
+    /// The value in the slot must be fully initialized.
+    #[inline]
+    pub unsafe fn commit(self) {
+        self.ssq.publish();
+        mem::forget(self);
+    }
+
+    /// Write `val` into the slot, and publish it to the consumer.
+    #[inline]
+    pub fn commit_with(mut self, val: T) {
+        self.write(val);
+        // Safety: the value was just written.
+        unsafe { self.commit() }
+    }
+}
+
+impl<'p, T, S: Storage<T>> Deref for WriteGrant<'p, T, S> {
+    type Target = MaybeUninit<T>;
+
+    #[inline]
+    fn deref(&self) -> &MaybeUninit<T> {
+        // Safety: the slot is held by the producer until the grant is committed or dropped.
+        unsafe { &*self.ssq.val.as_ptr().cast() }
+    }
+}
+
+impl<'p, T, S: Storage<T>> DerefMut for WriteGrant<'p, T, S> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut MaybeUninit<T> {
+        // Safety: the slot is held by the producer until the grant is committed or dropped.
+        unsafe { &mut *self.ssq.val.as_ptr().cast() }
+    }
+}
+
+/// Safety: The slot stays where it is when the grant is moved, and is held until the grant is
+/// committed or dropped.
+#[cfg(feature = "embedded-dma")]
+unsafe impl<'p, T: embedded_dma::WriteTarget, S: Storage<T>> embedded_dma::WriteBuffer
+    for WriteGrant<'p, T, S>
+{
+    type Word = T::Word;
+
+    #[inline]
+    unsafe fn write_buffer(&mut self) -> (*mut T::Word, usize) {
+        embedded_dma::WriteTarget::as_write_buffer(&mut **self)
+    }
+}
+
+impl<'p, T, S: Storage<T>> Drop for WriteGrant<'p, T, S> {
+    fn drop(&mut self) {
+        // Anything written in the slot is abandoned, as it may not be fully initialized.
+        self.ssq.release(EMPTY);
+    }
+}
+
+/// Releases the slot in state `to` when dropped, so that a user closure panicking while the slot
+/// is held does not leave the queue permanently busy. Forgotten once the closure has returned.
+struct Restore<'q, T, S: Storage<T>> {
+    ssq: &'q SingleSlotQueue<T, S>,
+    to: u8,
+}
+
+impl<'q, T, S: Storage<T>> Drop for Restore<'q, T, S> {
+    fn drop(&mut self) {
+        self.ssq.release(self.to);
+    }
+}
+
+/// Iterator dequeuing values until the queue is observed empty, returned by
+/// [`Consumer::drain`].
+pub struct Drain<'c, 'a, T, S: Storage<T> = Inline<T>> {
+    cons: &'c mut Consumer<'a, T, S>,
+}
+
+impl<'c, 'a, T, S: Storage<T>> Iterator for Drain<'c, 'a, T, S> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.cons.dequeue()
+    }
+}
+
+/// Write handle to a single slot queue.
+pub struct Producer<'a, T, S: Storage<T> = Inline<T>> {
+    ssq: &'a SingleSlotQueue<T, S>,
+}
+
+impl<'a, T, S: Storage<T>> Producer<'a, T, S> {
+    /// Write a value into the queue. If there is a value already in the queue, or the
+    /// corresponding [`Consumer`] has been dropped, this will return the value given to this
+    /// method.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        self.try_enqueue(val).err().map(EnqueueError::into_inner)
+    }
+
+    /// Write a value into the queue, reporting why it could not be written.
+    ///
+    /// Like [`enqueue`](Producer::enqueue), this method never blocks, and never overwrites a
+    /// value already in the queue. The value given to this method is handed back in the error.
+    #[inline]
+    pub fn try_enqueue(&mut self, val: T) -> Result<(), EnqueueError<T>> {
+        self.enqueue_with(|| val).map_err(|e| e.map(|f| f()))
+    }
+
+    /// Write a value into the queue without checking that it is empty.
+    ///
+    /// This skips the atomic compare-and-swap of [`enqueue`](Producer::enqueue), for hot paths
+    /// (e.g. an interrupt handler) where the caller already knows the queue is empty. The value is
+    /// written even if the corresponding [`Consumer`] has been dropped, and is then dropped along
+    /// with the queue.
+    ///
+    /// # Safety
+    ///
+    /// The queue must be empty, and the consumer must not be accessing it. Checking
+    /// [`is_empty`](Producer::is_empty) is not enough on its own, as it also returns `true` while
+    /// the consumer is in the middle of dequeuing a value; the caller must also know that the
+    /// consumer cannot be running, for instance because it runs at a lower priority, or because
+    /// it is the same context. This is checked with a debug assertion.
+    #[inline]
+    pub unsafe fn enqueue_unchecked(&mut self, val: T) {
+        debug_assert!(self.ssq.state.load(Ordering::Relaxed) == EMPTY);
+        // Make sure the consumer is done reading the previous value, as the compare-and-swap
+        // would.
+        self.ssq.state.load(Ordering::Acquire);
+        ptr::write(self.ssq.val.as_ptr(), val);
+        self.ssq.publish();
+    }
+
+    /// Construct a value directly in the queue with `f`, so that large payloads (e.g.
+    /// `[u8; 1024]` frames) need not be built on the stack and copied in.
+    ///
+    /// `f` is only called once the slot is known to be empty and reserved for the producer. If
+    /// there is a value already in the queue, or the corresponding [`Consumer`] is accessing the
+    /// slot or has been dropped, `f` is not called and is handed back in the error instead. Like
+    /// [`enqueue`](Producer::enqueue), this method never blocks.
+    ///
+    /// If `f` panics, the queue is left empty.
+    #[inline]
+    pub fn enqueue_with<F: FnOnce() -> T>(&mut self, f: F) -> Result<(), EnqueueError<F>> {
+        if let Err(e) = self.reserve() {
+            return Err(e.map(|()| f));
+        }
+
+        // Gives the slot back, still empty, if `f` panics.
+        let grant = WriteGrant { ssq: self.ssq };
+        grant.commit_with(f());
+        Ok(())
+    }
+
+    /// Initialize a value directly in the queue with `init`, for payloads too large to be built
+    /// anywhere else on a stack-constrained target.
+    ///
+    /// As with [`enqueue_with`](Producer::enqueue_with), `init` is only run once the slot is
+    /// known to be empty and reserved for the producer, and is handed back in the outer error
+    /// otherwise. If `init` itself fails, the queue is left empty and its error is returned in
+    /// the inner result. The queue is also left empty if `init` panics.
+    pub fn enqueue_init<I: Init<T, E>, E>(
+        &mut self,
+        init: I,
+    ) -> Result<Result<(), E>, EnqueueError<I>> {
+        if let Err(e) = self.reserve() {
+            return Err(e.map(|()| init));
+        }
+
+        // Gives the slot back, still empty, if `init` fails or panics.
+        let grant = WriteGrant { ssq: self.ssq };
+        match unsafe { init.init(self.ssq.val.as_ptr()) } {
+            Ok(()) => {
+                // Safety: `init` succeeded, so the value is fully initialized.
+                unsafe { grant.commit() };
+                Ok(Ok(()))
+            }
+            Err(e) => Ok(Err(e)),
+        }
+    }
+
+    /// Take exclusive access to the slot if it is empty and the consumer is still connected,
+    /// so that a value can be written into it.
+    #[inline]
+    fn reserve(&mut self) -> Result<(), EnqueueError<()>> {
+        let err = if !self.is_consumer_connected() {
+            EnqueueError::Disconnected(())
+        } else {
+            match self.ssq.try_acquire(EMPTY) {
+                Ok(()) => return Ok(()),
+                Err(WRITING) => EnqueueError::WouldBlock(()),
+                Err(_) => EnqueueError::Full(()),
+            }
+        };
+
+        self.ssq.rejected();
+        Err(err)
+    }
+
+    /// Write a value into the queue, overwriting the old value if it exists.
+    ///
+    /// Returns [`Overwrite::Overwrote`] with the old value if it was overwritten, so that it is
+    /// dropped (or reused) by the caller rather than leaked.
+    ///
+    /// This method never blocks. If the corresponding [`Consumer`] is in the middle of
+    /// [`dequeue`](Consumer::dequeue)ing or [`peek`](Consumer::peek)ing, the slot cannot be
+    /// written to and the value given to this method is handed back in
+    /// [`Overwrite::Rejected`] instead. The value is also rejected if the consumer has been
+    /// dropped.
+    #[inline]
+    pub fn enqueue_overwrite(&mut self, val: T) -> Overwrite<T> {
+        match self.try_enqueue_overwrite(val) {
+            Ok(None) => Overwrite::Enqueued,
+            Ok(Some(old)) => Overwrite::Overwrote(old),
+            Err(e) => Overwrite::Rejected(e.into_inner()),
+        }
+    }
+
+    /// Write a value into the queue, overwriting the old value if it exists, reporting why it
+    /// could not be written.
+    ///
+    /// Returns the old value if it was overwritten. Like
+    /// [`enqueue_overwrite`](Producer::enqueue_overwrite), this method never blocks, so it is
+    /// bounded-time even in a high-rate interrupt handler: if the corresponding [`Consumer`] is in
+    /// the middle of reading the slot, it gives up and hands `val` back in
+    /// `Err(EnqueueError::WouldBlock(_))` instead. The value is also handed back, in
+    /// `Err(EnqueueError::Disconnected(_))`, if the consumer has been dropped.
+    #[inline]
+    pub fn try_enqueue_overwrite(&mut self, val: T) -> Result<Option<T>, EnqueueError<T>> {
+        if !self.is_consumer_connected() {
+            self.ssq.rejected();
+            return Err(EnqueueError::Disconnected(val));
+        }
+
+        let old = match self.ssq.acquire_any() {
+            None => {
+                self.ssq.rejected();
+                return Err(EnqueueError::WouldBlock(val));
+            }
+            Some(FULL) => Some(unsafe { ptr::read(self.ssq.val.as_ptr()) }),
+            Some(_) => None,
+        };
+
+        unsafe { ptr::write(self.ssq.val.as_ptr(), val) };
+        self.ssq.publish();
+        if old.is_some() {
+            self.ssq.overwrote();
+        }
+        Ok(old)
+    }
+
+    /// Install a value in the queue, and take back the value that was pending, if any, so that
+    /// its buffer can be reused without a separate recycle queue.
+    ///
+    /// This is [`try_enqueue_overwrite`](Producer::try_enqueue_overwrite) without the reason
+    /// for failing: if the corresponding [`Consumer`] is in the middle of reading the slot, or
+    /// has been dropped, `val` is handed back in `Err`.
+    #[inline]
+    pub fn swap(&mut self, val: T) -> Result<Option<T>, T> {
+        self.try_enqueue_overwrite(val)
+            .map_err(EnqueueError::into_inner)
+    }
+
+    /// Write a value into the queue, overwriting the old value if it exists, and report whether
+    /// any data was lost.
+    ///
+    /// Unlike [`enqueue_overwrite`](Producer::enqueue_overwrite), the value that did not make it
+    /// into the queue (if any) is dropped, which makes this convenient for counting lost
+    /// messages.
+    #[inline]
+    pub fn enqueue_lossy(&mut self, val: T) -> LossyEnqueue {
+        match self.try_enqueue_overwrite(val) {
+            Ok(None) => LossyEnqueue::Enqueued,
+            Ok(Some(_)) => LossyEnqueue::Overwrote,
+            Err(_) => LossyEnqueue::Dropped,
+        }
+    }
+
+    /// Write a value into the queue, overwriting the old value if it exists, and send the old
+    /// value into `recycle` so that its owner can reclaim it (e.g. a buffer from a pool).
+    ///
+    /// Returns the value that did not make it into either queue: the value given to this method
+    /// if it could not be written (as with [`enqueue_overwrite`](Producer::enqueue_overwrite)),
+    /// or the old value if `recycle` is full or disconnected.
+    pub fn enqueue_overwrite_recycle<S2: Storage<T>>(
+        &mut self,
+        val: T,
+        recycle: &mut Producer<'_, T, S2>,
+    ) -> Option<T> {
+        match self.try_enqueue_overwrite(val) {
+            Ok(old) => old.and_then(|old| recycle.enqueue(old)),
+            Err(e) => Some(e.into_inner()),
+        }
+    }
+
+    /// Write a value into the queue, merging it into the old value if it exists.
+    ///
+    /// If there is an unread value in the queue, it is replaced with `merge(old, val)`, so that
+    /// no data is lost (e.g. summing counters, or keeping the highest severity). Like
+    /// [`enqueue_overwrite`](Producer::enqueue_overwrite), this never blocks: if the
+    /// corresponding [`Consumer`] is in the middle of reading the slot, or has been dropped,
+    /// the value given to this method is returned.
+    ///
+    /// If `merge` panics, the old value is dropped, and the queue is left empty.
+    pub fn enqueue_merge(&mut self, val: T, merge: impl FnOnce(T, T) -> T) -> Option<T> {
+        let state = if self.is_consumer_connected() {
+            self.ssq.acquire_any()
+        } else {
+            None
+        };
+
+        let val = match state {
+            None => {
+                self.ssq.rejected();
+                return Some(val);
+            }
+            Some(FULL) => {
+                // The old value is moved out, so the slot is empty until the merged value is
+                // written back.
+                let restore = Restore {
+                    ssq: self.ssq,
+                    to: EMPTY,
+                };
+                let val = merge(unsafe { ptr::read(self.ssq.val.as_ptr()) }, val);
+                mem::forget(restore);
+                val
+            }
+            Some(_) => val,
+        };
+
+        unsafe { ptr::write(self.ssq.val.as_ptr(), val) };
+        self.ssq.publish();
+        None
+    }
+
+    /// Write a value into the queue, for use in `nb`-style polling drivers and with
+    /// [`nb::block!`].
+    ///
+    /// Returns `Err(nb::Error::WouldBlock)` if there is a value already in the queue, or the
+    /// corresponding [`Consumer`] is in the middle of reading the slot, and
+    /// `Err(nb::Error::Other(Disconnected(val)))` if the consumer has been dropped. Since
+    /// `block!` retries with the same value, `T` must be [`Copy`].
+    #[cfg(feature = "nb")]
+    #[inline]
+    pub fn enqueue_nb(&mut self, val: T) -> nb::Result<(), Disconnected<T>>
+    where
+        T: Copy,
+    {
+        match self.try_enqueue(val) {
+            Ok(()) => Ok(()),
+            Err(EnqueueError::Disconnected(v)) => Err(nb::Error::Other(Disconnected(v))),
+            Err(_) => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    /// Get exclusive access to the empty slot, to build a value directly in it (e.g. with a DMA
+    /// transfer or a parser) before publishing it with [`commit`](WriteGrant::commit).
+    ///
+    /// This never blocks: if there is a value already in the queue, or the corresponding
+    /// [`Consumer`] is accessing the slot or has been dropped, `None` is returned. Dropping the
+    /// grant without committing it leaves the queue empty.
+    ///
+    /// If the grant is leaked, the queue is left permanently busy.
+    #[inline]
+    pub fn write_grant(&mut self) -> Option<WriteGrant<'_, T, S>> {
+        self.reserve().ok()?;
+        Some(WriteGrant { ssq: self.ssq })
+    }
+
+    /// Get exclusive access to the empty slot, and a pointer to it, so that a DMA peripheral can
+    /// write a value straight into it. The value is then published with
+    /// [`commit_written`](Producer::commit_written).
+    ///
+    /// Returns `None` if there is a value already in the queue, or the corresponding [`Consumer`]
+    /// is accessing the slot or has been dropped. The queue stays busy until the value is
+    /// committed.
+    ///
+    /// # Safety
+    ///
+    /// [`commit_written`](Producer::commit_written) must eventually be called, once the value has
+    /// been fully written, or the queue is left permanently busy.
+    #[inline]
+    pub unsafe fn write_ptr(&mut self) -> Option<*mut T> {
+        self.reserve().ok()?;
+        Some(self.ssq.val.as_ptr())
+    }
+
+    /// Publish the value written through the pointer returned by
+    /// [`write_ptr`](Producer::write_ptr).
+    ///
+    /// # Safety
+    ///
+    /// The slot must have been taken with `write_ptr` and not committed since, and the value in
+    /// it must be fully initialized, with the writes to it complete (e.g. the DMA transfer must
+    /// be done). The former is checked with a debug assertion.
+    #[inline]
+    pub unsafe fn commit_written(&mut self) {
+        debug_assert!(self.ssq.state.load(Ordering::Relaxed) == WRITING);
+        self.ssq.publish();
+    }
+
+    /// Take back the value in the queue, if the corresponding [`Consumer`] has not read it yet, so
+    /// that it can be withdrawn (e.g. a command that no longer applies).
+    ///
+    /// This never blocks: if the consumer is in the middle of reading the value, it is too late to
+    /// withdraw it, and `None` is returned.
+    #[inline]
+    pub fn purge(&mut self) -> Option<T> {
+        self.ssq.reclaim()
+    }
+
+    /// Update the unread value in the queue in place with `modify`, or enqueue the value returned
+    /// by `insert` if the queue is empty, and return `true`.
+    ///
+    /// This lets the producer accumulate into a value the consumer has not read yet (e.g.
+    /// appending samples to a batch), instead of choosing between rejecting the new data and
+    /// overwriting the old. Like [`enqueue_merge`](Producer::enqueue_merge), this never blocks:
+    /// if the corresponding [`Consumer`] is in the middle of reading the slot, or has been
+    /// dropped, neither closure is called and `false` is returned.
+    ///
+    /// If either closure panics, the queue is left as it was: holding the value as `modify` left
+    /// it, or empty.
+    pub fn modify(&mut self, modify: impl FnOnce(&mut T), insert: impl FnOnce() -> T) -> bool {
+        let state = if self.is_consumer_connected() {
+            self.ssq.acquire_any()
+        } else {
+            None
+        };
+
+        let Some(state) = state else {
+            self.ssq.rejected();
+            return false;
+        };
+
+        let restore = Restore {
+            ssq: self.ssq,
+            to: state,
+        };
+        if state == FULL {
+            modify(unsafe { &mut *self.ssq.val.as_ptr() });
+        } else {
+            unsafe { ptr::write(self.ssq.val.as_ptr(), insert()) };
+        }
+        mem::forget(restore);
+
+        self.ssq.publish();
+        true
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ssq.state.load(Ordering::Relaxed) != FULL
+    }
+
+    /// Check if there is a value in the queue, the opposite of
+    /// [`is_empty`](Self::is_empty).
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.ssq.state.load(Ordering::Relaxed) == FULL
+    }
+
+    /// Check if the corresponding [`Consumer`] is still around to read enqueued values.
+    #[inline]
+    pub fn is_consumer_connected(&self) -> bool {
+        !self.ssq.rx_closed.load(Ordering::Acquire)
+    }
+
+    /// Snapshot of the queue's traffic counters.
+    #[cfg(feature = "stats")]
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.ssq.stats.snapshot()
+    }
+
+    /// Snapshot of the queue's contention counters.
+    #[cfg(feature = "contention")]
+    #[inline]
+    pub fn contention(&self) -> Contention {
+        self.ssq.stats.contention()
+    }
+
+    /// Write a value into the queue, and wait for the corresponding [`Consumer`] to dequeue it.
+    ///
+    /// This busy-waits, first for the queue to be empty, then for the value to be taken out, so
+    /// that neither side can run ahead of the other. If the consumer is dropped before taking
+    /// the value, it is returned in `Err(Disconnected(_))`.
+    pub fn enqueue_rendezvous(&mut self, mut val: T) -> Result<(), Disconnected<T>> {
+        while let Some(v) = self.enqueue(val) {
+            if !self.is_consumer_connected() {
+                return Err(Disconnected(v));
+            }
+            val = v;
+            hint::spin_loop();
+        }
+
+        loop {
+            match self.poll_taken() {
+                Some(r) => return r,
+                None => hint::spin_loop(),
+            }
+        }
+    }
+
+    /// Wait for the queue to be empty, enqueue a value, and wait for the corresponding
+    /// [`Consumer`] to dequeue it.
+    ///
+    /// If the consumer is dropped before taking the value, it is returned in
+    /// `Err(Disconnected(_))`. If the returned future is dropped after the value was enqueued
+    /// but before it was dequeued, the value stays in the queue.
+    #[cfg(feature = "async")]
+    pub async fn enqueue_rendezvous_async(&mut self, val: T) -> Result<(), Disconnected<T>> {
+        self.enqueue_async(val).await?;
+        core::future::poll_fn(|cx| {
+            if let Some(r) = self.poll_taken() {
+                return Poll::Ready(r);
+            }
+
+            self.ssq.tx_waker.register(cx.waker());
+
+            // Check again, in case the consumer took the value before the waker was registered.
+            match self.poll_taken() {
+                Some(r) => Poll::Ready(r),
+                None => Poll::Pending,
+            }
+        })
+        .await
+    }
+
+    /// Check if an enqueued value has been taken out by the consumer, reclaiming it if the
+    /// consumer is gone.
+    #[inline]
+    fn poll_taken(&mut self) -> Option<Result<(), Disconnected<T>>> {
+        // The consumer holds the slot while peeking at the value, which is still queued then.
+        if self.ssq.state.load(Ordering::Acquire) == EMPTY {
+            Some(Ok(()))
+        } else if !self.is_consumer_connected() {
+            // The consumer may have dequeued the value right before being dropped.
+            Some(self.ssq.reclaim().map_or(Ok(()), |v| Err(Disconnected(v))))
+        } else {
+            None
+        }
+    }
+
+    /// Wait for the queue to be empty, and enqueue a value.
+    ///
+    /// The returned future registers the task's waker with the queue, and is woken by the
+    /// corresponding [`Consumer`] when it dequeues a value. If the consumer is dropped, the
+    /// future resolves to `Err(Disconnected(val))`. Dropping the future before it completes
+    /// drops `val`.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn enqueue_async(&mut self, val: T) -> EnqueueFuture<'_, 'a, T, S> {
+        EnqueueFuture {
+            prod: self,
+            val: Some(val),
+        }
+    }
+
+    /// Poll for room in the queue, for use in hand-written futures.
+    ///
+    /// Takes the value out of `val` and enqueues it if the queue is empty, then returns
+    /// `Poll::Ready(Ok(()))`. If the corresponding [`Consumer`] has been dropped, takes the value
+    /// out of `val` and returns it in `Poll::Ready(Err(Disconnected(_)))`. Otherwise, leaves
+    /// `val` untouched, registers the task's waker to be woken when the consumer dequeues a
+    /// value or is dropped, and returns `Poll::Pending`. Only the waker from the most recent
+    /// call is woken.
+    ///
+    /// Returns `Poll::Ready(Ok(()))` immediately if `val` is `None`.
+    #[cfg(feature = "async")]
+    pub fn poll_enqueue(
+        &mut self,
+        cx: &mut Context<'_>,
+        val: &mut Option<T>,
+    ) -> Poll<Result<(), Disconnected<T>>> {
+        let Some(v) = val.take() else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let v = match self.enqueue(v) {
+            None => return Poll::Ready(Ok(())),
+            Some(v) => v,
+        };
+
+        self.ssq.tx_waker.register(cx.waker());
+
+        // Check again, in case the consumer emptied the queue before the waker was registered.
+        match self.enqueue(v) {
+            None => Poll::Ready(Ok(())),
+            Some(v) if !self.is_consumer_connected() => Poll::Ready(Err(Disconnected(v))),
+            Some(v) => {
+                *val = Some(v);
+                Poll::Pending
+            }
+        }
+    }
+
+    /// Turn this producer into a [`Sink`](futures_sink::Sink), which handles items sent to
+    /// a full queue according to `behavior`.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn into_sink(self, behavior: FullBehavior) -> ProducerSink<'a, T, S> {
+        ProducerSink {
+            prod: self,
+            behavior,
+            pending: None,
+        }
+    }
+}
+
+/// Enqueues each item with [`enqueue_lossy`](Producer::enqueue_lossy), so that the last item
+/// wins unless the [`Consumer`] reads an earlier one first. Items that could not be written are
+/// dropped.
+impl<'a, T, S: Storage<T>> Extend<T> for Producer<'a, T, S> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.enqueue_lossy(val);
+        }
+    }
+}
+
+/// Shows the state of the slot, and the value it holds if the consumer is not in the middle of
+/// reading it.
+impl<'a, T: fmt::Debug, S: Storage<T>> fmt::Debug for Producer<'a, T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("Producer");
+        self.ssq.debug_slot(&mut d);
+        d.field("consumer_connected", &self.is_consumer_connected())
+            .finish()
+    }
+}
+
+/// Formats the state of the slot, but not the value it may hold.
+#[cfg(feature = "defmt")]
+impl<'a, T, S: Storage<T>> defmt::Format for Producer<'a, T, S> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        defmt::write!(
+            f,
+            "Producer {{ state: {}, consumer_connected: {=bool} }}",
+            self.ssq.slot_state(),
+            self.is_consumer_connected()
+        );
+    }
+}
+
+/// Formats the state of the slot, but not the value it may hold.
+#[cfg(feature = "ufmt")]
+impl<'a, T, S: Storage<T>> ufmt::uDebug for Producer<'a, T, S> {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        f.debug_struct("Producer")?
+            .field("state", &self.ssq.slot_state())?
+            .field("consumer_connected", &self.is_consumer_connected())?
+            .finish()
+    }
+}
+
+impl<'a, T, S: Storage<T>> Drop for Producer<'a, T, S> {
+    fn drop(&mut self) {
+        self.ssq.tx_closed.store(true, Ordering::Release);
+        #[cfg(feature = "log")]
+        log::debug!("producer dropped, queue closed");
+        #[cfg(feature = "async")]
+        self.ssq.rx_waker.wake();
     }
 }
 
-/// Safety: We gurarantee the safety using an `AtomicBool` to gate the write of the
-/// `UnsafeCell`.
-unsafe impl<'a, T> Send for Producer<'a, T> {}
+/// Safety: We gurarantee the safety using an `AtomicU8` to gate the write of the
+/// `UnsafeCell`. The value is handed over to another thread, and the storage is shared with the
+/// consumer.
+unsafe impl<'a, T: Send, S: Storage<T> + Sync> Send for Producer<'a, T, S> {}
@@ -0,0 +1,144 @@
+//! Queue lending values to the consumer, with ownership flowing back to the producer once the
+//! consumer is done, e.g. for round-tripping `&'static mut` buffers.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::loan::LoanedQueue;
+//! let buf: &'static mut [u8; 4] = Box::leak(Box::new([0; 4]));
+//!
+//! let mut queue = LoanedQueue::<&'static mut [u8; 4]>::new();
+//! let (mut cons, mut prod) = queue.split();
+//!
+//! buf[0] = 1;
+//! assert!(prod.lend(buf).is_none());
+//!
+//! let loan = cons.borrow().unwrap();
+//! assert!(loan[0] == 1);
+//! assert!(prod.reclaim().is_none());
+//!
+//! // Dropping the loan sends the buffer back to the producer.
+//! drop(loan);
+//! assert!(prod.reclaim().unwrap()[0] == 1);
+//! ```
+
+use crate::{slot::RawSlot, Consumer as SsqConsumer, Producer as SsqProducer, SingleSlotQueue};
+use core::{
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+
+/// Queue lending values from its [`Producer`] to its [`Consumer`], with a return slot for the
+/// values to come back through.
+pub struct LoanedQueue<T> {
+    queue: SingleSlotQueue<T>,
+    returned: RawSlot<T>,
+}
+
+impl<T> LoanedQueue<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        LoanedQueue {
+            queue: SingleSlotQueue::new(),
+            returned: RawSlot::new(),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        let (cons, prod) = self.queue.split();
+        (
+            Consumer {
+                cons,
+                returned: &self.returned,
+            },
+            Producer {
+                prod,
+                returned: &self.returned,
+            },
+        )
+    }
+}
+
+/// Read handle to a loaned queue.
+pub struct Consumer<'a, T> {
+    cons: SsqConsumer<'a, T>,
+    returned: &'a RawSlot<T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Try borrowing the value lent by the [`Producer`].
+    ///
+    /// The value is sent back to the producer when the returned loan is dropped. A value is
+    /// only borrowed once the previously returned one has been
+    /// [`reclaim`](Producer::reclaim)ed, so that there is always room for it to go back.
+    #[inline]
+    pub fn borrow(&mut self) -> Option<Loan<'_, T>> {
+        if self.returned.is_full() {
+            return None;
+        }
+
+        let val = self.cons.dequeue()?;
+        Some(Loan {
+            val: ManuallyDrop::new(val),
+            returned: self.returned,
+        })
+    }
+
+    /// Check if there is a value waiting to be borrowed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+}
+
+/// Value borrowed from a loaned queue, returned to the [`Producer`] when dropped.
+pub struct Loan<'b, T> {
+    val: ManuallyDrop<T>,
+    returned: &'b RawSlot<T>,
+}
+
+impl<'b, T> Deref for Loan<'b, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.val
+    }
+}
+
+impl<'b, T> DerefMut for Loan<'b, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.val
+    }
+}
+
+impl<'b, T> Drop for Loan<'b, T> {
+    fn drop(&mut self) {
+        let val = unsafe { ManuallyDrop::take(&mut self.val) };
+        // The consumer checked that the return slot was empty before borrowing, and only it
+        // ever fills the slot, so there is room.
+        let _ = self.returned.put(val);
+    }
+}
+
+/// Write handle to a loaned queue.
+pub struct Producer<'a, T> {
+    prod: SsqProducer<'a, T>,
+    returned: &'a RawSlot<T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Lend a value to the [`Consumer`]. If there is a value already waiting to be borrowed, or
+    /// the consumer has been dropped, this will return the value given to this method.
+    #[inline]
+    pub fn lend(&mut self, val: T) -> Option<T> {
+        self.prod.enqueue(val)
+    }
+
+    /// Take back a value the [`Consumer`] is done with, if any.
+    #[inline]
+    pub fn reclaim(&mut self) -> Option<T> {
+        self.returned.take()
+    }
+}
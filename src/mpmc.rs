@@ -0,0 +1,75 @@
+//! Multi-producer, multi-consumer single slot cell.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{mpmc::AtomicSlot, Overwrite};
+//! static SLOT: AtomicSlot<u32> = AtomicSlot::new();
+//!
+//! // Any thread may put a value in, or take it out.
+//! assert!(SLOT.put(1) == None);
+//! assert!(SLOT.put(2) == Some(2));
+//! assert!(SLOT.take() == Some(1));
+//! assert!(SLOT.take() == None);
+//!
+//! // Or the last writer wins, getting back the value it displaced.
+//! assert!(SLOT.put_overwrite(3) == Overwrite::Enqueued);
+//! assert!(SLOT.put_overwrite(4) == Overwrite::Overwrote(3));
+//! ```
+
+use crate::{slot::RawSlot, Overwrite};
+
+/// Single slot which any thread may put a value into or take it out of.
+///
+/// Unlike the queues, there is no split into handles: the slot can live in a `static` and be
+/// shared by reference. Operations never block; when two of them race, one wins and the other
+/// acts as if the slot was full (for writes) or empty (for reads).
+pub struct AtomicSlot<T> {
+    slot: RawSlot<T>,
+}
+
+impl<T> AtomicSlot<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        AtomicSlot {
+            slot: RawSlot::new(),
+        }
+    }
+
+    /// Put a value into the slot. If there is a value already in the slot, or another thread is
+    /// accessing it, this will return the value given to this method.
+    #[inline]
+    pub fn put(&self, val: T) -> Option<T> {
+        self.slot.put(val)
+    }
+
+    /// Put a value into the slot, overwriting the old value if it exists.
+    ///
+    /// Returns the old value in [`Overwrite::Overwrote`] if it was overwritten, or the value given
+    /// to this method in [`Overwrite::Rejected`] if another thread is accessing the slot.
+    #[inline]
+    pub fn put_overwrite(&self, val: T) -> Overwrite<T> {
+        self.slot.replace(val)
+    }
+
+    /// Take the value out of the slot, if there is one and no other thread is accessing it.
+    #[inline]
+    pub fn take(&self) -> Option<T> {
+        self.slot.take()
+    }
+
+    /// Check if there is a value in the slot.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.is_full()
+    }
+}
+
+impl<T: Copy> AtomicSlot<T> {
+    /// Copy the value out of the slot without taking it, if there is one and no other thread is
+    /// accessing it.
+    #[inline]
+    pub fn peek(&self) -> Option<T> {
+        self.slot.peek()
+    }
+}
@@ -0,0 +1,105 @@
+//! Multi-producer, single-consumer single slot queue.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{mpsc::MpscSlot, Overwrite};
+//! let mut slot = MpscSlot::<u32>::new();
+//! let (mut cons, mut prod) = slot.split();
+//! let mut prod2 = prod.clone();
+//!
+//! // Producers compete for the slot; the first one to claim it wins.
+//! assert!(prod.enqueue(1) == None);
+//! assert!(prod2.enqueue(2) == Some(2));
+//!
+//! // Or the last writer wins, handing back the value it displaced.
+//! assert!(prod2.enqueue_overwrite(3) == Overwrite::Overwrote(1));
+//! assert!(cons.dequeue() == Some(3));
+//! ```
+
+use crate::{slot::RawSlot, Overwrite};
+
+/// Single slot queue with any number of producers and a single consumer.
+pub struct MpscSlot<T> {
+    slot: RawSlot<T>,
+}
+
+impl<T> MpscSlot<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        MpscSlot {
+            slot: RawSlot::new(),
+        }
+    }
+
+    /// Split the queue into its consumer and a first producer. More producers can be created by
+    /// cloning it.
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (Consumer { slot: &self.slot }, Producer { slot: &self.slot })
+    }
+}
+
+/// Read handle to a multi-producer single slot queue.
+pub struct Consumer<'a, T> {
+    slot: &'a RawSlot<T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Try reading a value from the queue.
+    ///
+    /// This method never blocks. If a [`Producer`] is in the middle of writing a value, that
+    /// value is not published yet and `None` is returned.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.slot.take()
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.is_full()
+    }
+}
+
+impl<'a, T: Copy> Consumer<'a, T> {
+    /// Try reading a value without dequeuing.
+    #[inline]
+    pub fn peek(&mut self) -> Option<T> {
+        self.slot.peek()
+    }
+}
+
+/// Write handle to a multi-producer single slot queue.
+pub struct Producer<'a, T> {
+    slot: &'a RawSlot<T>,
+}
+
+impl<'a, T> Clone for Producer<'a, T> {
+    fn clone(&self) -> Self {
+        Producer { slot: self.slot }
+    }
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Write a value into the queue. If there is a value already in the queue, or another
+    /// handle is accessing it, this will return the value given to this method.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        self.slot.put(val)
+    }
+
+    /// Write a value into the queue, overwriting the old value if it exists.
+    ///
+    /// Returns the old value in [`Overwrite::Overwrote`] if it was overwritten, or the value given
+    /// to this method in [`Overwrite::Rejected`] if another handle is accessing the slot.
+    #[inline]
+    pub fn enqueue_overwrite(&mut self, val: T) -> Overwrite<T> {
+        self.slot.replace(val)
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.is_full()
+    }
+}
@@ -0,0 +1,125 @@
+//! Event signal carrying no data, for when a `SingleSlotQueue<()>` would only be used as a
+//! flag.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::notify::Notifier;
+//! static EVENT: Notifier = Notifier::new();
+//!
+//! // Typically called from an interrupt handler.
+//! EVENT.notify();
+//! EVENT.notify();
+//!
+//! // Notifications do not accumulate.
+//! assert!(EVENT.take());
+//! assert!(!EVENT.take());
+//! ```
+
+use atomic_polyfill::{AtomicBool, Ordering};
+#[cfg(feature = "async")]
+use core::task::{Context, Poll};
+
+#[cfg(feature = "async")]
+use crate::waker::WakerCell;
+
+/// Flag which any number of notifiers may set, and a single listener clears.
+pub struct Notifier {
+    flag: AtomicBool,
+    #[cfg(feature = "async")]
+    waker: WakerCell,
+    /// Set while a [`Listener`] exists.
+    #[cfg(feature = "async")]
+    listening: AtomicBool,
+}
+
+impl Notifier {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Notifier {
+            flag: AtomicBool::new(false),
+            #[cfg(feature = "async")]
+            waker: WakerCell::new(),
+            #[cfg(feature = "async")]
+            listening: AtomicBool::new(false),
+        }
+    }
+
+    /// Set the flag, waking the listener if it is waiting.
+    #[inline]
+    pub fn notify(&self) {
+        self.flag.store(true, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.waker.wake();
+    }
+
+    /// Clear the flag, returning whether it was set.
+    #[inline]
+    pub fn take(&self) -> bool {
+        self.flag.swap(false, Ordering::Acquire)
+    }
+
+    /// Check if the flag is set, without clearing it.
+    #[inline]
+    pub fn is_notified(&self) -> bool {
+        self.flag.load(Ordering::Relaxed)
+    }
+
+    /// Claim the right to wait for notifications, for the single task listening on the
+    /// notifier.
+    ///
+    /// Returns `None` if the notifier already has a listener; it can be claimed again once that
+    /// one is dropped.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn listen(&self) -> Option<Listener<'_>> {
+        self.listening
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| Listener { notifier: self })
+    }
+}
+
+/// Handle of the task waiting on a [`Notifier`], returned by [`Notifier::listen`].
+///
+/// There is at most one listener per notifier, so it is always the one woken by a notification.
+#[cfg(feature = "async")]
+pub struct Listener<'a> {
+    notifier: &'a Notifier,
+}
+
+#[cfg(feature = "async")]
+impl<'a> Listener<'a> {
+    /// Wait for the flag to be set, and clear it.
+    pub async fn wait(&mut self) {
+        core::future::poll_fn(|cx| self.poll_wait(cx)).await
+    }
+
+    /// Poll for the flag to be set, for use in hand-written futures.
+    ///
+    /// Clears the flag and returns `Poll::Ready(())` if it is set. Otherwise, registers the
+    /// task's waker to be woken by the next notification, and returns `Poll::Pending`.
+    pub fn poll_wait(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let notifier = self.notifier;
+        if notifier.take() {
+            return Poll::Ready(());
+        }
+
+        notifier.waker.register(cx.waker());
+
+        // Check again, in case a notification came in before the waker was registered.
+        if notifier.take() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'a> Drop for Listener<'a> {
+    fn drop(&mut self) {
+        self.notifier.waker.clear();
+        self.notifier.listening.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,168 @@
+//! Single-use channel, for signalling the completion of a request, e.g. from an interrupt
+//! handler to the task waiting on it.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::oneshot::Channel;
+//! let mut chan = Channel::<u32>::new();
+//! let (mut cons, prod) = chan.split();
+//!
+//! assert!(cons.dequeue() == None);
+//!
+//! // Enqueuing consumes the producer, so at most one value is ever sent.
+//! assert!(prod.enqueue(1) == None);
+//! assert!(cons.dequeue() == Some(1));
+//! assert!(cons.is_closed());
+//! ```
+
+use crate::slot::RawSlot;
+use atomic_polyfill::{AtomicBool, Ordering};
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "async")]
+use crate::{waker::WakerCell, Closed};
+
+/// Channel carrying at most one value from its [`Producer`] to its [`Consumer`].
+pub struct Channel<T> {
+    slot: RawSlot<T>,
+    /// Set when the [`Producer`] is consumed or dropped.
+    tx_closed: AtomicBool,
+    /// Set when the [`Consumer`] is dropped.
+    rx_closed: AtomicBool,
+    #[cfg(feature = "async")]
+    rx_waker: WakerCell,
+}
+
+impl<T> Channel<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Channel {
+            slot: RawSlot::new(),
+            tx_closed: AtomicBool::new(false),
+            rx_closed: AtomicBool::new(false),
+            #[cfg(feature = "async")]
+            rx_waker: WakerCell::new(),
+        }
+    }
+
+    /// Split the channel into its consumer and producer.
+    ///
+    /// The channel can be split again once both handles are gone; any value left over from the
+    /// previous split is dropped.
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        drop(self.slot.take());
+        *self.tx_closed.get_mut() = false;
+        *self.rx_closed.get_mut() = false;
+        (Consumer { chan: self }, Producer { chan: self })
+    }
+}
+
+/// Read handle to a oneshot channel.
+pub struct Consumer<'a, T> {
+    chan: &'a Channel<T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Try reading the value out of the channel.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.chan.slot.take()
+    }
+
+    /// Check if the corresponding [`Producer`] is gone, either because it sent its value or
+    /// because it was dropped without sending one.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.chan.tx_closed.load(Ordering::Acquire)
+    }
+
+    /// Poll for the value, for use in hand-written futures.
+    ///
+    /// Returns `Poll::Ready(Ok(_))` with the value if it has been sent, or
+    /// `Poll::Ready(Err(Closed))` if the corresponding [`Producer`] was dropped without sending
+    /// one (or the value was already dequeued). Otherwise, registers the task's waker and
+    /// returns `Poll::Pending`.
+    #[cfg(feature = "async")]
+    pub fn poll_dequeue(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, Closed>> {
+        if let Some(v) = self.dequeue() {
+            return Poll::Ready(Ok(v));
+        }
+
+        self.chan.rx_waker.register(cx.waker());
+
+        // Check again, in case the producer sent its value before the waker was registered.
+        if let Some(v) = self.dequeue() {
+            return Poll::Ready(Ok(v));
+        }
+
+        if self.is_closed() {
+            return Poll::Ready(self.dequeue().ok_or(Closed));
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Awaiting the consumer resolves to the value once it has been sent, or to `Err(Closed)` if the
+/// [`Producer`] was dropped without sending one.
+#[cfg(feature = "async")]
+impl<'a, T> Future for Consumer<'a, T> {
+    type Output = Result<T, Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, Closed>> {
+        self.get_mut().poll_dequeue(cx)
+    }
+}
+
+impl<'a, T> Drop for Consumer<'a, T> {
+    fn drop(&mut self) {
+        self.chan.rx_closed.store(true, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.chan.rx_waker.clear();
+    }
+}
+
+/// Safety: The value is only ever read once the producer has published it.
+unsafe impl<'a, T: Send> Send for Consumer<'a, T> {}
+
+/// Write handle to a oneshot channel.
+pub struct Producer<'a, T> {
+    chan: &'a Channel<T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Send the value, closing the channel. If the corresponding [`Consumer`] has been dropped,
+    /// this will return the value given to this method.
+    #[inline]
+    pub fn enqueue(self, val: T) -> Option<T> {
+        if !self.is_consumer_connected() {
+            return Some(val);
+        }
+
+        // The consumer only ever takes a value out, so the empty slot cannot be contended.
+        self.chan.slot.put(val)
+    }
+
+    /// Check if the corresponding [`Consumer`] is still around to receive the value.
+    #[inline]
+    pub fn is_consumer_connected(&self) -> bool {
+        !self.chan.rx_closed.load(Ordering::Acquire)
+    }
+}
+
+impl<'a, T> Drop for Producer<'a, T> {
+    fn drop(&mut self) {
+        self.chan.tx_closed.store(true, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.chan.rx_waker.wake();
+    }
+}
+
+/// Safety: The value is only ever written once, before the producer is dropped.
+unsafe impl<'a, T: Send> Send for Producer<'a, T> {}
@@ -0,0 +1,171 @@
+//! Ping-pong buffer, for peripherals which fill one half of a buffer while the application
+//! processes the other (e.g. ADC or audio DMA with half-complete interrupts).
+//!
+//! The two halves are handed back and forth between the [`Producer`] and the [`Consumer`]:
+//! each half is owned by exactly one of them at a time, and the consumer reads the halves in the
+//! order they were committed.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::pingpong::PingPong;
+//! let mut buf = PingPong::<u16, 4>::new(0);
+//! let (mut cons, mut prod) = buf.split();
+//!
+//! prod.buffer().unwrap().fill(1);
+//! assert!(prod.commit());
+//! prod.buffer().unwrap().fill(2);
+//! assert!(prod.commit());
+//!
+//! // Both halves are full, so the producer has nowhere left to write.
+//! assert!(prod.buffer().is_none());
+//!
+//! // Dropping the half hands it back to the producer.
+//! assert!(*cons.read().unwrap() == [1; 4]);
+//! assert!(prod.buffer().is_some());
+//! assert!(*cons.read().unwrap() == [2; 4]);
+//! assert!(cons.read().is_none());
+//! ```
+
+use atomic_polyfill::{AtomicBool, Ordering};
+use core::{cell::UnsafeCell, ops::Deref};
+
+/// Buffer made of two halves of `N` values each.
+///
+/// The halves are contiguous in memory, so a circular DMA transfer can be pointed at the whole
+/// buffer with [`Producer::as_mut_ptr`].
+pub struct PingPong<T, const N: usize> {
+    halves: UnsafeCell<[[T; N]; 2]>,
+    /// Set when a half has been committed by the producer, and not yet released by the
+    /// consumer.
+    full: [AtomicBool; 2],
+}
+
+impl<T: Copy, const N: usize> PingPong<T, N> {
+    /// Create a ping-pong buffer with both halves filled with `init`.
+    pub const fn new(init: T) -> Self {
+        PingPong {
+            halves: UnsafeCell::new([[init; N]; 2]),
+            full: [AtomicBool::new(false), AtomicBool::new(false)],
+        }
+    }
+}
+
+impl<T, const N: usize> PingPong<T, N> {
+    pub fn split(&mut self) -> (Consumer<'_, T, N>, Producer<'_, T, N>) {
+        *self.full[0].get_mut() = false;
+        *self.full[1].get_mut() = false;
+        (
+            Consumer { buf: self, next: 0 },
+            Producer { buf: self, next: 0 },
+        )
+    }
+
+    #[inline]
+    fn half(&self, idx: usize) -> *mut [T; N] {
+        // SAFETY: `idx` is 0 or 1, and the pointer stays within `halves`.
+        unsafe { self.halves.get().cast::<[T; N]>().add(idx) }
+    }
+}
+
+/// Read handle to a ping-pong buffer.
+pub struct Consumer<'a, T, const N: usize> {
+    buf: &'a PingPong<T, N>,
+    /// Index of the next half to be read.
+    next: usize,
+}
+
+impl<'a, T, const N: usize> Consumer<'a, T, N> {
+    /// Check if there is a committed half waiting to be read.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.buf.full[self.next].load(Ordering::Relaxed)
+    }
+
+    /// Borrow the oldest committed half, if any.
+    ///
+    /// The half is handed back to the [`Producer`] when the returned guard is dropped.
+    #[inline]
+    pub fn read(&mut self) -> Option<Half<'_, 'a, T, N>> {
+        if self.buf.full[self.next].load(Ordering::Acquire) {
+            Some(Half { cons: self })
+        } else {
+            None
+        }
+    }
+}
+
+/// Safety: Each half is only ever accessed by the handle that owns it, as gated by `full`.
+unsafe impl<'a, T: Send, const N: usize> Send for Consumer<'a, T, N> {}
+
+/// Borrow of a committed half, returned by [`Consumer::read`].
+pub struct Half<'b, 'a, T, const N: usize> {
+    cons: &'b mut Consumer<'a, T, N>,
+}
+
+impl<'b, 'a, T, const N: usize> Deref for Half<'b, 'a, T, N> {
+    type Target = [T; N];
+
+    #[inline]
+    fn deref(&self) -> &[T; N] {
+        // SAFETY: The half is owned by the consumer until the guard is dropped.
+        unsafe { &*self.cons.buf.half(self.cons.next) }
+    }
+}
+
+impl<'b, 'a, T, const N: usize> Drop for Half<'b, 'a, T, N> {
+    fn drop(&mut self) {
+        self.cons.buf.full[self.cons.next].store(false, Ordering::Release);
+        self.cons.next ^= 1;
+    }
+}
+
+/// Write handle to a ping-pong buffer.
+pub struct Producer<'a, T, const N: usize> {
+    buf: &'a PingPong<T, N>,
+    /// Index of the half being filled.
+    next: usize,
+}
+
+impl<'a, T, const N: usize> Producer<'a, T, N> {
+    /// Borrow the half being filled, if the [`Consumer`] has released it.
+    #[inline]
+    pub fn buffer(&mut self) -> Option<&mut [T; N]> {
+        if self.buf.full[self.next].load(Ordering::Acquire) {
+            None
+        } else {
+            // SAFETY: The half is owned by the producer until it is committed.
+            Some(unsafe { &mut *self.buf.half(self.next) })
+        }
+    }
+
+    /// Hand the half being filled over to the [`Consumer`], and move on to the other half.
+    ///
+    /// Call this from the half-complete and complete interrupts of a circular DMA transfer.
+    /// If the consumer has not released the half yet, the peripheral has overrun it: nothing is
+    /// committed and `false` is returned.
+    #[inline]
+    pub fn commit(&mut self) -> bool {
+        let full = &self.buf.full[self.next];
+        if full.load(Ordering::Acquire) {
+            return false;
+        }
+
+        full.store(true, Ordering::Release);
+        self.next ^= 1;
+        true
+    }
+
+    /// Raw pointer to the start of the buffer, to point a peripheral at both halves (`2 * N`
+    /// values).
+    ///
+    /// The peripheral may only write to the half being filled before it is
+    /// [`commit`](Producer::commit)ed.
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.halves.get().cast()
+    }
+}
+
+/// Safety: Each half is only ever accessed by the handle that owns it, as gated by `full`.
+unsafe impl<'a, T: Send, const N: usize> Send for Producer<'a, T, N> {}
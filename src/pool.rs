@@ -0,0 +1,125 @@
+//! Fixed pool of slots handing out leases which return their slot on drop, so that large
+//! payloads can be passed around without allocating, and without moving them through a queue.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{pool::{Lease, SlotPool}, SingleSlotQueue};
+//! static POOL: SlotPool<[u8; 256], 2> = SlotPool::new();
+//!
+//! let mut queue = SingleSlotQueue::<Lease<'static, [u8; 256]>>::new();
+//! let (mut cons, mut prod) = queue.split();
+//!
+//! // Only the lease goes through the queue.
+//! let mut frame = POOL.alloc([0; 256]).ok().unwrap();
+//! frame[0] = 42;
+//! assert!(prod.enqueue(frame).is_none());
+//! assert!(POOL.available() == 1);
+//!
+//! let frame = cons.dequeue().unwrap();
+//! assert!(frame[0] == 42);
+//!
+//! // Dropping the lease returns its slot to the pool.
+//! drop(frame);
+//! assert!(POOL.available() == 2);
+//! ```
+
+use atomic_polyfill::{AtomicBool, Ordering};
+use core::{
+    cell::UnsafeCell,
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// Pool of `N` slots for values of type `T`.
+pub struct SlotPool<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    /// Set while a slot is leased out.
+    used: [AtomicBool; N],
+}
+
+/// Safety: A slot is only ever accessed through the single lease holding it, as gated by
+/// `used`.
+unsafe impl<T: Send, const N: usize> Sync for SlotPool<T, N> {}
+
+impl<T, const N: usize> SlotPool<T, N> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        SlotPool {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            used: [const { AtomicBool::new(false) }; N],
+        }
+    }
+
+    /// Move `val` into a free slot, and lease it out. If all slots are leased out, this will
+    /// return the value given to this method.
+    pub fn alloc(&self, val: T) -> Result<Lease<'_, T>, T> {
+        for (slot, used) in self.slots.iter().zip(&self.used) {
+            if used
+                .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_ok()
+            {
+                unsafe { ptr::write(slot.get().cast(), val) };
+                return Ok(Lease { slot, used });
+            }
+        }
+        Err(val)
+    }
+
+    /// The number of slots which are not leased out.
+    pub fn available(&self) -> usize {
+        self.used
+            .iter()
+            .filter(|used| !used.load(Ordering::Relaxed))
+            .count()
+    }
+}
+
+/// Exclusive handle to a value held in a [`SlotPool`], returning its slot to the pool when
+/// dropped.
+pub struct Lease<'a, T> {
+    slot: &'a UnsafeCell<MaybeUninit<T>>,
+    used: &'a AtomicBool,
+}
+
+impl<'a, T> Lease<'a, T> {
+    /// Move the value out of the pool, returning its slot.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        let val = unsafe { ptr::read(self.slot.get().cast()) };
+        self.used.store(false, Ordering::Release);
+        mem::forget(self);
+        val
+    }
+}
+
+impl<'a, T> Deref for Lease<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // SAFETY: The slot was initialized when leased out, and only this lease accesses it.
+        unsafe { &*self.slot.get().cast() }
+    }
+}
+
+impl<'a, T> DerefMut for Lease<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: The slot was initialized when leased out, and only this lease accesses it.
+        unsafe { &mut *self.slot.get().cast() }
+    }
+}
+
+impl<'a, T> Drop for Lease<'a, T> {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.slot.get().cast::<T>()) };
+        self.used.store(false, Ordering::Release);
+    }
+}
+
+/// Safety: The lease has exclusive access to its value.
+unsafe impl<'a, T: Send> Send for Lease<'a, T> {}
+/// Safety: The lease only hands out shared references to its value from `&self`.
+unsafe impl<'a, T: Sync> Sync for Lease<'a, T> {}
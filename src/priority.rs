@@ -0,0 +1,96 @@
+//! Two-slot mailbox, with an urgent slot that is always dequeued first.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::priority::PriorityMailbox;
+//! let mut mailbox = PriorityMailbox::<u32>::new();
+//! let (mut cons, mut prod) = mailbox.split();
+//!
+//! assert!(prod.enqueue(1) == None);
+//! assert!(prod.enqueue_urgent(2) == None);
+//!
+//! // The urgent value jumps ahead of the routine one.
+//! assert!(cons.dequeue() == Some(2));
+//! assert!(cons.dequeue() == Some(1));
+//! assert!(cons.dequeue() == None);
+//! ```
+
+use crate::slot::RawSlot;
+
+/// Mailbox holding up to one routine and one urgent value.
+pub struct PriorityMailbox<T> {
+    high: RawSlot<T>,
+    low: RawSlot<T>,
+}
+
+impl<T> PriorityMailbox<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        PriorityMailbox {
+            high: RawSlot::new(),
+            low: RawSlot::new(),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (Consumer { mailbox: self }, Producer { mailbox: self })
+    }
+}
+
+/// Read handle to a priority mailbox.
+pub struct Consumer<'a, T> {
+    mailbox: &'a PriorityMailbox<T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Try reading a value from the mailbox, urgent values first.
+    ///
+    /// This method never blocks. A slot the [`Producer`] is in the middle of writing is skipped.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.mailbox.high.take().or_else(|| self.mailbox.low.take())
+    }
+
+    /// Check if there is no value in either slot.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.mailbox.high.is_full() && !self.mailbox.low.is_full()
+    }
+}
+
+impl<'a, T: Copy> Consumer<'a, T> {
+    /// Try reading the value [`dequeue`](Consumer::dequeue) would return, without dequeuing.
+    #[inline]
+    pub fn peek(&mut self) -> Option<T> {
+        self.mailbox.high.peek().or_else(|| self.mailbox.low.peek())
+    }
+}
+
+/// Write handle to a priority mailbox.
+pub struct Producer<'a, T> {
+    mailbox: &'a PriorityMailbox<T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Write a routine value into the mailbox. If there is a routine value already in the
+    /// mailbox, this will return the value given to this method.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        self.mailbox.low.put(val)
+    }
+
+    /// Write an urgent value into the mailbox, to be dequeued before any routine value. If
+    /// there is an urgent value already in the mailbox, this will return the value given to
+    /// this method.
+    #[inline]
+    pub fn enqueue_urgent(&mut self, val: T) -> Option<T> {
+        self.mailbox.high.put(val)
+    }
+
+    /// Check if there is no value in either slot.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.mailbox.high.is_full() && !self.mailbox.low.is_full()
+    }
+}
@@ -0,0 +1,136 @@
+//! Producer adapter letting at most one value through per period, so that a chatty interrupt
+//! handler cannot flood the consumer with redundant updates.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{ratelimit::RateLimited, timestamp::Clock, SingleSlotQueue};
+//! use std::cell::Cell;
+//!
+//! struct FakeClock(Cell<u32>);
+//! impl Clock for FakeClock {
+//!     type Instant = u32;
+//!     fn now(&self) -> u32 {
+//!         self.0.get()
+//!     }
+//!     // Tick counters wrap around.
+//!     fn duration_since(&self, later: u32, earlier: u32) -> u32 {
+//!         later.wrapping_sub(earlier)
+//!     }
+//! }
+//!
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! let (mut cons, prod) = queue.split();
+//! let mut prod = RateLimited::new(prod, FakeClock(Cell::new(0)), 10);
+//!
+//! assert!(prod.enqueue(1) == None);
+//! assert!(cons.dequeue() == Some(1));
+//!
+//! // Too soon: the value is handed back.
+//! assert!(prod.enqueue(2) == Some(2));
+//!
+//! // Or held back, and sent once the period has elapsed.
+//! assert!(prod.enqueue_latest(3) == None);
+//! prod.clock().0.set(10);
+//! assert!(prod.flush());
+//! assert!(cons.dequeue() == Some(3));
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    timestamp::Clock,
+    Producer,
+};
+use core::ops::Sub;
+
+/// Producer letting at most one value through per period.
+pub struct RateLimited<'a, T, C, S = Inline<T>>
+where
+    C: Clock,
+    C::Instant: Sub,
+    S: Storage<T>,
+{
+    prod: Producer<'a, T, S>,
+    clock: C,
+    period: <C::Instant as Sub>::Output,
+    /// When the last value was let through.
+    last: Option<C::Instant>,
+    /// The latest value held back by [`enqueue_latest`](RateLimited::enqueue_latest).
+    held: Option<T>,
+}
+
+impl<'a, T, C, S> RateLimited<'a, T, C, S>
+where
+    C: Clock,
+    C::Instant: Sub,
+    <C::Instant as Sub>::Output: PartialOrd,
+    S: Storage<T>,
+{
+    /// Wrap `prod`, letting at most one value through every `period`, as measured by `clock`.
+    #[inline]
+    pub fn new(prod: Producer<'a, T, S>, clock: C, period: <C::Instant as Sub>::Output) -> Self {
+        RateLimited {
+            prod,
+            clock,
+            period,
+            last: None,
+            held: None,
+        }
+    }
+
+    /// Write a value into the queue, as with [`Producer::enqueue`], if the period has elapsed
+    /// since the last value was let through. Otherwise, this will return the value given to
+    /// this method.
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        let now = self.clock.now();
+        if !self.is_ready(now) {
+            return Some(val);
+        }
+
+        let r = self.prod.enqueue(val);
+        if r.is_none() {
+            self.last = Some(now);
+        }
+        r
+    }
+
+    /// Hold a value back until the period has elapsed, replacing any value held back earlier,
+    /// and try to [`flush`](RateLimited::flush) it.
+    ///
+    /// Returns the value held back earlier, if it was replaced.
+    pub fn enqueue_latest(&mut self, val: T) -> Option<T> {
+        let old = self.held.replace(val);
+        self.flush();
+        old
+    }
+
+    /// Write the value held back by [`enqueue_latest`](RateLimited::enqueue_latest) into the
+    /// queue, if the period has elapsed. Returns whether there is no value held back anymore.
+    ///
+    /// This needs to be called regularly for a held back value to be let through when no new
+    /// values are enqueued.
+    pub fn flush(&mut self) -> bool {
+        if let Some(val) = self.held.take() {
+            self.held = self.enqueue(val);
+        }
+        self.held.is_none()
+    }
+
+    /// The clock used to measure the period.
+    #[inline]
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Give back the wrapped producer, along with the value held back, if any.
+    #[inline]
+    pub fn into_inner(self) -> (Producer<'a, T, S>, Option<T>) {
+        (self.prod, self.held)
+    }
+
+    #[inline]
+    fn is_ready(&self, now: C::Instant) -> bool {
+        self.last
+            .is_none_or(|last| self.clock.duration_since(now, last) >= self.period)
+    }
+}
@@ -0,0 +1,371 @@
+//! Multi-slot single-producer, single-consumer ring buffer.
+//!
+//! [`SlotQueue`] holds its values inline, with a capacity fixed at compile time.
+//! [`SliceSlotQueue`] holds its values in a caller-provided slice. With the `alloc` feature,
+//! [`HeapSlotQueue`] allocates its values on the heap, with a capacity chosen at runtime. All of
+//! them split into the same [`Consumer`] and [`Producer`] handles.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::ring::SlotQueue;
+//! let mut queue = SlotQueue::<u32, 2>::new();
+//! let (mut cons, mut prod) = queue.split();
+//!
+//! assert!(prod.enqueue(1) == None);
+//! assert!(prod.enqueue(2) == None);
+//!
+//! // `enqueue` returns `Some(t)` when the queue is already full.
+//! assert!(prod.enqueue(3) == Some(3));
+//!
+//! // Values are dequeued in the order they were enqueued.
+//! assert!(cons.dequeue() == Some(1));
+//! assert!(cons.dequeue() == Some(2));
+//! assert!(cons.dequeue() == None);
+//! ```
+
+use atomic_polyfill::{AtomicUsize, Ordering};
+use core::{cell::UnsafeCell, marker::PhantomData, mem::MaybeUninit, ptr};
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+
+/// The shared state of a ring buffer, borrowed by its two handles.
+///
+/// `head` and `tail` count positions modulo `2 * cap`, so that a full ring (`tail - head == cap`)
+/// can be told apart from an empty one (`tail == head`) without sacrificing a slot. The consumer
+/// only ever writes `head`, and the producer only ever writes `tail`.
+struct Ring<'a, T> {
+    head: &'a AtomicUsize,
+    tail: &'a AtomicUsize,
+    buf: *mut T,
+    cap: usize,
+    _marker: PhantomData<&'a UnsafeCell<T>>,
+}
+
+impl<'a, T> Ring<'a, T> {
+    #[inline]
+    fn slot(&self, pos: usize) -> *mut T {
+        let idx = if pos >= self.cap { pos - self.cap } else { pos };
+        // SAFETY: `idx < cap`, and `buf` points to `cap` slots.
+        unsafe { self.buf.add(idx) }
+    }
+
+    #[inline]
+    fn next(&self, pos: usize) -> usize {
+        if pos + 1 == 2 * self.cap {
+            0
+        } else {
+            pos + 1
+        }
+    }
+
+    #[inline]
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        if tail >= head {
+            tail - head
+        } else {
+            tail + 2 * self.cap - head
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        self.distance(head, tail)
+    }
+
+    #[inline]
+    fn push(&self, val: T) -> Option<T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if self.distance(head, tail) == self.cap {
+            return Some(val);
+        }
+
+        unsafe { ptr::write(self.slot(tail), val) };
+        self.tail.store(self.next(tail), Ordering::Release);
+        None
+    }
+
+    /// Get the slot at the front of the ring, if any.
+    #[inline]
+    fn front(&self) -> Option<*mut T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            None
+        } else {
+            Some(self.slot(head))
+        }
+    }
+
+    #[inline]
+    fn pop(&self) -> Option<T> {
+        let slot = self.front()?;
+        let val = unsafe { ptr::read(slot) };
+        let head = self.head.load(Ordering::Relaxed);
+        self.head.store(self.next(head), Ordering::Release);
+        Some(val)
+    }
+
+    /// Drop all values left in the ring. Neither handle may be alive.
+    fn drop_all(&self) {
+        while self.pop().is_some() {}
+    }
+}
+
+/// Single-producer, single-consumer queue holding up to `N` values.
+pub struct SlotQueue<T, const N: usize> {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    buf: UnsafeCell<MaybeUninit<[T; N]>>,
+}
+
+impl<T, const N: usize> SlotQueue<T, N> {
+    /// Create an empty queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is 0.
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        assert!(N > 0, "`SlotQueue` must have room for at least one value");
+        SlotQueue {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            buf: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (
+            Consumer { ring: self.ring() },
+            Producer { ring: self.ring() },
+        )
+    }
+
+    /// The number of values the queue can hold.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn ring(&self) -> Ring<'_, T> {
+        Ring {
+            head: &self.head,
+            tail: &self.tail,
+            buf: self.buf.get().cast(),
+            cap: N,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for SlotQueue<T, N> {
+    fn drop(&mut self) {
+        self.ring().drop_all();
+    }
+}
+
+/// Single-producer, single-consumer queue holding its values in caller-provided storage, e.g.
+/// placed in a specific RAM bank or linker section.
+pub struct SliceSlotQueue<'s, T> {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    buf: *mut T,
+    cap: usize,
+    _marker: PhantomData<&'s mut [MaybeUninit<T>]>,
+}
+
+impl<'s, T> SliceSlotQueue<'s, T> {
+    /// Create an empty queue holding up to `storage.len()` values in `storage`.
+    ///
+    /// Any values left in the queue are dropped along with it; `storage` is never read before a
+    /// value has been written to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `storage` is empty.
+    pub fn from_storage(storage: &'s mut [MaybeUninit<T>]) -> Self {
+        assert!(
+            !storage.is_empty(),
+            "`SliceSlotQueue` must have room for at least one value"
+        );
+        SliceSlotQueue {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            cap: storage.len(),
+            buf: storage.as_mut_ptr().cast(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (
+            Consumer { ring: self.ring() },
+            Producer { ring: self.ring() },
+        )
+    }
+
+    /// The number of values the queue can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    fn ring(&self) -> Ring<'_, T> {
+        Ring {
+            head: &self.head,
+            tail: &self.tail,
+            buf: self.buf,
+            cap: self.cap,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'s, T> Drop for SliceSlotQueue<'s, T> {
+    fn drop(&mut self) {
+        self.ring().drop_all();
+    }
+}
+
+/// Single-producer, single-consumer queue with a capacity chosen at runtime.
+#[cfg(feature = "alloc")]
+pub struct HeapSlotQueue<T> {
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    buf: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> HeapSlotQueue<T> {
+    /// Create an empty queue, allocating room for `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "`HeapSlotQueue` must have room for at least one value"
+        );
+        HeapSlotQueue {
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            buf: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (
+            Consumer { ring: self.ring() },
+            Producer { ring: self.ring() },
+        )
+    }
+
+    /// The number of values the queue can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    fn ring(&self) -> Ring<'_, T> {
+        Ring {
+            head: &self.head,
+            tail: &self.tail,
+            // `UnsafeCell<MaybeUninit<T>>` has the same layout as `T`.
+            buf: UnsafeCell::raw_get(self.buf.as_ptr()).cast(),
+            cap: self.buf.len(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Drop for HeapSlotQueue<T> {
+    fn drop(&mut self) {
+        self.ring().drop_all();
+    }
+}
+
+/// Read handle to a ring buffer.
+pub struct Consumer<'a, T> {
+    ring: Ring<'a, T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Try reading the oldest value from the queue.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.ring.pop()
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of values in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// The number of values the queue can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ring.cap
+    }
+}
+
+impl<'a, T: Copy> Consumer<'a, T> {
+    /// Try reading the oldest value without dequeuing.
+    #[inline]
+    pub fn peek(&mut self) -> Option<T> {
+        self.ring.front().map(|slot| unsafe { ptr::read(slot) })
+    }
+}
+
+/// Safety: The consumer only reads slots that the producer has published by advancing `tail`.
+unsafe impl<'a, T: Send> Send for Consumer<'a, T> {}
+
+/// Write handle to a ring buffer.
+pub struct Producer<'a, T> {
+    ring: Ring<'a, T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Write a value into the queue. If the queue is full this will return the value given to
+    /// this method.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        self.ring.push(val)
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of values in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    /// The number of values the queue can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.ring.cap
+    }
+}
+
+/// Safety: The producer only writes slots that the consumer has released by advancing `head`.
+unsafe impl<'a, T: Send> Send for Producer<'a, T> {}
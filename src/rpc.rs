@@ -0,0 +1,178 @@
+//! Request/response channel made of two single slot queues, for command/acknowledge patterns
+//! (e.g. a task sending commands to an interrupt handler).
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::rpc::RpcChannel;
+//! let mut chan = RpcChannel::<u32, bool>::new();
+//! let (mut caller, mut handler) = chan.split();
+//!
+//! assert!(caller.request(4) == None);
+//!
+//! // Typically called from an interrupt handler.
+//! assert!(handler.handle(|req| req % 2 == 0));
+//!
+//! assert!(caller.response() == Some(true));
+//! ```
+
+#[cfg(feature = "async")]
+use crate::{Closed, Disconnected};
+use crate::{Consumer, Producer, SingleSlotQueue};
+#[cfg(feature = "async")]
+use core::fmt;
+
+/// Error returned by [`Caller::call`].
+#[cfg(feature = "async")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub enum CallError<Req> {
+    /// The [`Handler`] has been dropped, and the request could not be sent.
+    Disconnected(Req),
+    /// The [`Handler`] was dropped without responding to the request.
+    Closed,
+}
+
+#[cfg(feature = "async")]
+impl<Req> fmt::Display for CallError<Req> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CallError::Disconnected(_) => "handler disconnected",
+            CallError::Closed => "handler dropped without responding",
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Req: fmt::Debug> core::error::Error for CallError<Req> {}
+
+/// Channel carrying requests from a [`Caller`] to a [`Handler`], and responses back.
+pub struct RpcChannel<Req, Resp> {
+    req: SingleSlotQueue<Req>,
+    resp: SingleSlotQueue<Resp>,
+}
+
+impl<Req, Resp> RpcChannel<Req, Resp> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        RpcChannel {
+            req: SingleSlotQueue::new(),
+            resp: SingleSlotQueue::new(),
+        }
+    }
+
+    pub fn split(&mut self) -> (Caller<'_, Req, Resp>, Handler<'_, Req, Resp>) {
+        let (req_cons, req_prod) = self.req.split();
+        let (resp_cons, resp_prod) = self.resp.split();
+        (
+            Caller {
+                req: req_prod,
+                resp: resp_cons,
+            },
+            Handler {
+                req: req_cons,
+                resp: resp_prod,
+            },
+        )
+    }
+}
+
+/// Handle sending requests and receiving their responses.
+pub struct Caller<'a, Req, Resp> {
+    req: Producer<'a, Req>,
+    resp: Consumer<'a, Resp>,
+}
+
+impl<'a, Req, Resp> Caller<'a, Req, Resp> {
+    /// Send a request. If the previous request has not been picked up by the [`Handler`] yet,
+    /// or the handler has been dropped, this will return the request given to this method.
+    #[inline]
+    pub fn request(&mut self, req: Req) -> Option<Req> {
+        self.req.enqueue(req)
+    }
+
+    /// Try reading a response.
+    #[inline]
+    pub fn response(&mut self) -> Option<Resp> {
+        self.resp.dequeue()
+    }
+
+    /// Check if the [`Handler`] is still around to handle requests.
+    #[inline]
+    pub fn is_handler_connected(&self) -> bool {
+        self.req.is_consumer_connected()
+    }
+
+    /// Send a request once the previous one has been picked up, and wait for its response.
+    ///
+    /// Any response left over from an earlier request must have been read beforehand, or it will
+    /// be mistaken for the response to this one.
+    #[cfg(feature = "async")]
+    pub async fn call(&mut self, req: Req) -> Result<Resp, CallError<Req>> {
+        self.req
+            .enqueue_async(req)
+            .await
+            .map_err(|Disconnected(req)| CallError::Disconnected(req))?;
+        self.resp
+            .dequeue_async()
+            .await
+            .map_err(|Closed| CallError::Closed)
+    }
+}
+
+/// Handle receiving requests and sending back their responses.
+pub struct Handler<'a, Req, Resp> {
+    req: Consumer<'a, Req>,
+    resp: Producer<'a, Resp>,
+}
+
+impl<'a, Req, Resp> Handler<'a, Req, Resp> {
+    /// Try reading a request.
+    #[inline]
+    pub fn request(&mut self) -> Option<Req> {
+        self.req.dequeue()
+    }
+
+    /// Send a response. If the previous response has not been read by the [`Caller`] yet, or the
+    /// caller has been dropped, this will return the response given to this method.
+    #[inline]
+    pub fn respond(&mut self, resp: Resp) -> Option<Resp> {
+        self.resp.enqueue(resp)
+    }
+
+    /// Handle a pending request with `f`, and send back its response.
+    ///
+    /// The response slot is reserved before the request is taken out of the channel, so the
+    /// request is left pending rather than its response lost if there is no room for it (e.g.
+    /// the [`Caller`] is in the middle of reading the previous response, or has been dropped).
+    /// Returns whether a request was handled.
+    pub fn handle(&mut self, f: impl FnOnce(Req) -> Resp) -> bool {
+        let Some(grant) = self.resp.write_grant() else {
+            return false;
+        };
+
+        match self.req.dequeue() {
+            Some(req) => {
+                grant.commit_with(f(req));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check if the [`Caller`] is still around to send requests.
+    #[inline]
+    pub fn is_caller_connected(&self) -> bool {
+        self.resp.is_consumer_connected()
+    }
+
+    /// Wait for a request.
+    ///
+    /// Once the [`Caller`] has been dropped and its final request (if any) read, resolves to
+    /// `Err(Closed)`.
+    #[cfg(feature = "async")]
+    pub async fn request_async(&mut self) -> Result<Req, Closed> {
+        self.req.dequeue_async().await
+    }
+}
@@ -0,0 +1,66 @@
+//! Timeout variants of the async methods, generic over RTIC 2 [`Monotonic`]s.
+
+use crate::{
+    future::{DequeueTimeoutError, EnqueueTimeoutError},
+    storage::Storage,
+    Closed, Consumer, Disconnected, Producer,
+};
+use core::future::poll_fn;
+use rtic_time::{Monotonic, TimeoutError};
+
+impl<'a, T, S: Storage<T>> Consumer<'a, T, S> {
+    /// Like [`dequeue_async`](Consumer::dequeue_async), but gives up with
+    /// `Err(DequeueTimeoutError::Timeout)` if no value was enqueued by `deadline`, as measured
+    /// by the monotonic `M`.
+    pub async fn dequeue_timeout_at<M: Monotonic>(
+        &mut self,
+        deadline: M::Instant,
+    ) -> Result<T, DequeueTimeoutError> {
+        match M::timeout_at(deadline, self.dequeue_async()).await {
+            Ok(Ok(v)) => Ok(v),
+            Ok(Err(Closed)) => Err(DequeueTimeoutError::Closed),
+            Err(TimeoutError) => Err(DequeueTimeoutError::Timeout),
+        }
+    }
+
+    /// Like [`dequeue_timeout_at`](Consumer::dequeue_timeout_at), with the deadline set
+    /// `timeout` from now.
+    pub async fn dequeue_timeout_after<M: Monotonic>(
+        &mut self,
+        timeout: M::Duration,
+    ) -> Result<T, DequeueTimeoutError> {
+        self.dequeue_timeout_at::<M>(M::now() + timeout).await
+    }
+}
+
+impl<'a, T, S: Storage<T>> Producer<'a, T, S> {
+    /// Like [`enqueue_async`](Producer::enqueue_async), but gives up with
+    /// `Err(EnqueueTimeoutError::Timeout(val))` if the queue did not become empty by
+    /// `deadline`, as measured by the monotonic `M`.
+    pub async fn enqueue_timeout_at<M: Monotonic>(
+        &mut self,
+        val: T,
+        deadline: M::Instant,
+    ) -> Result<(), EnqueueTimeoutError<T>> {
+        let mut val = Some(val);
+        match M::timeout_at(deadline, poll_fn(|cx| self.poll_enqueue(cx, &mut val))).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(Disconnected(v))) => Err(EnqueueTimeoutError::Disconnected(v)),
+            Err(TimeoutError) => {
+                self.ssq.tx_waker.clear();
+                // `poll_enqueue` only takes the value out when it resolves.
+                Err(EnqueueTimeoutError::Timeout(val.take().unwrap()))
+            }
+        }
+    }
+
+    /// Like [`enqueue_timeout_at`](Producer::enqueue_timeout_at), with the deadline set
+    /// `timeout` from now.
+    pub async fn enqueue_timeout_after<M: Monotonic>(
+        &mut self,
+        val: T,
+        timeout: M::Duration,
+    ) -> Result<(), EnqueueTimeoutError<T>> {
+        self.enqueue_timeout_at::<M>(val, M::now() + timeout).await
+    }
+}
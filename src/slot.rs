@@ -0,0 +1,125 @@
+//! The tri-state slot protocol, for the queue variants which hold their value inline.
+//!
+//! Every transition out of [`EMPTY`] or [`FULL`] is a compare-and-swap, so any number of threads
+//! may race on the same slot: exactly one of them gains exclusive access, and the others back
+//! off without spinning.
+
+use crate::Overwrite;
+use atomic_polyfill::{AtomicU8, Ordering};
+use core::{cell::UnsafeCell, mem::MaybeUninit, ptr};
+
+/// The slot holds no value.
+const EMPTY: u8 = 0;
+/// Someone has exclusive access to the slot, and is either writing a value in or reading one
+/// out.
+const WRITING: u8 = 1;
+/// The slot holds a value ready to be read.
+const FULL: u8 = 2;
+
+pub(crate) struct RawSlot<T> {
+    state: AtomicU8,
+    val: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// Safety: Access to the `UnsafeCell` is gated by the `AtomicU8`.
+unsafe impl<T: Send> Sync for RawSlot<T> {}
+
+impl<T> RawSlot<T> {
+    pub(crate) const fn new() -> Self {
+        RawSlot {
+            state: AtomicU8::new(EMPTY),
+            val: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    #[inline]
+    fn acquire(&self, from: u8) -> bool {
+        self.state
+            .compare_exchange(from, WRITING, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    #[inline]
+    fn release(&self, to: u8) {
+        self.state.store(to, Ordering::Release);
+    }
+
+    #[inline]
+    pub(crate) fn is_full(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == FULL
+    }
+
+    /// Write a value into the slot if it is empty, otherwise return it.
+    #[inline]
+    pub(crate) fn put(&self, val: T) -> Option<T> {
+        if self.acquire(EMPTY) {
+            unsafe { ptr::write(self.val.get().cast(), val) };
+            self.release(FULL);
+            None
+        } else {
+            Some(val)
+        }
+    }
+
+    /// Write a value into the slot, handing back the value it displaced, if any. If someone else
+    /// has exclusive access to the slot, `val` is rejected and handed back instead.
+    pub(crate) fn replace(&self, val: T) -> Overwrite<T> {
+        let mut current = self.state.load(Ordering::Relaxed);
+        let old = loop {
+            if current == WRITING {
+                return Overwrite::Rejected(val);
+            }
+
+            match self.state.compare_exchange(
+                current,
+                WRITING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(FULL) => {
+                    break Overwrite::Overwrote(unsafe { ptr::read(self.val.get().cast()) })
+                }
+                Ok(_) => break Overwrite::Enqueued,
+                Err(actual) => current = actual,
+            }
+        };
+
+        unsafe { ptr::write(self.val.get().cast(), val) };
+        self.release(FULL);
+        old
+    }
+
+    /// Take the value out of the slot, if there is one and no one else is accessing it.
+    #[inline]
+    pub(crate) fn take(&self) -> Option<T> {
+        if self.acquire(FULL) {
+            let r = unsafe { ptr::read(self.val.get().cast()) };
+            self.release(EMPTY);
+            Some(r)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Copy> RawSlot<T> {
+    /// Copy the value out of the slot, if there is one and no one else is accessing it.
+    #[inline]
+    pub(crate) fn peek(&self) -> Option<T> {
+        if self.acquire(FULL) {
+            let r = unsafe { ptr::read(self.val.get().cast()) };
+            self.release(FULL);
+            Some(r)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Drop for RawSlot<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == FULL {
+            unsafe { ptr::drop_in_place(self.val.get().cast::<T>()) };
+        }
+    }
+}
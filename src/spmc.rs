@@ -0,0 +1,107 @@
+//! Single-producer, multi-consumer single slot queue.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{spmc::SpmcSlot, Overwrite};
+//! let mut slot = SpmcSlot::<u32>::new();
+//! let (mut cons, mut prod) = slot.split();
+//! let mut cons2 = cons.clone();
+//!
+//! assert!(prod.enqueue(1) == None);
+//!
+//! // Consumers compete for the value; the first one to claim it wins.
+//! assert!(cons2.dequeue() == Some(1));
+//! assert!(cons.dequeue() == None);
+//!
+//! // Or the producer replaces the unread value, getting it back.
+//! assert!(prod.enqueue_overwrite(2) == Overwrite::Enqueued);
+//! assert!(prod.enqueue_overwrite(3) == Overwrite::Overwrote(2));
+//! ```
+
+use crate::{slot::RawSlot, Overwrite};
+
+/// Single slot queue with a single producer and any number of consumers.
+pub struct SpmcSlot<T> {
+    slot: RawSlot<T>,
+}
+
+impl<T> SpmcSlot<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        SpmcSlot {
+            slot: RawSlot::new(),
+        }
+    }
+
+    /// Split the queue into a first consumer and its producer. More consumers can be created by
+    /// cloning it.
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (Consumer { slot: &self.slot }, Producer { slot: &self.slot })
+    }
+}
+
+/// Read handle to a multi-consumer single slot queue.
+pub struct Consumer<'a, T> {
+    slot: &'a RawSlot<T>,
+}
+
+impl<'a, T> Clone for Consumer<'a, T> {
+    fn clone(&self) -> Self {
+        Consumer { slot: self.slot }
+    }
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Try reading a value from the queue.
+    ///
+    /// This method never blocks. If the [`Producer`] is in the middle of writing a value, or
+    /// another consumer is in the middle of reading it, `None` is returned.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.slot.take()
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.is_full()
+    }
+}
+
+impl<'a, T: Copy> Consumer<'a, T> {
+    /// Try reading a value without dequeuing.
+    #[inline]
+    pub fn peek(&mut self) -> Option<T> {
+        self.slot.peek()
+    }
+}
+
+/// Write handle to a multi-consumer single slot queue.
+pub struct Producer<'a, T> {
+    slot: &'a RawSlot<T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Write a value into the queue. If there is a value already in the queue this will
+    /// return the value given to this method.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        self.slot.put(val)
+    }
+
+    /// Write a value into the queue, overwriting the old value if it exists.
+    ///
+    /// Returns the old value in [`Overwrite::Overwrote`] if it was overwritten, or the value given
+    /// to this method in [`Overwrite::Rejected`] if a [`Consumer`] is accessing the slot.
+    #[inline]
+    pub fn enqueue_overwrite(&mut self, val: T) -> Overwrite<T> {
+        self.slot.replace(val)
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.slot.is_full()
+    }
+}
@@ -0,0 +1,219 @@
+//! Wrapper around [`SingleSlotQueue`] with the method names and semantics of `heapless::spsc`,
+//! so that code written against it can switch over without touching its call sites.
+//!
+//! As in `heapless::spsc`, a `Queue<T, N>` holds `N - 1` values. This queue holds a single value,
+//! so `N` must be 2, which is checked at compile time:
+//!
+//! ```compile_fail
+//! let queue = ssq::spsc::Queue::<u32, 4>::new();
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::spsc::Queue;
+//! let mut queue = Queue::<u32, 2>::new();
+//! let (mut producer, mut consumer) = queue.split();
+//!
+//! assert!(producer.ready());
+//! assert!(producer.enqueue(1) == Ok(()));
+//! assert!(producer.enqueue(2) == Err(2));
+//!
+//! assert!(consumer.peek() == Some(&1));
+//! assert!(consumer.dequeue() == Some(1));
+//! assert!(!consumer.ready());
+//! ```
+
+use crate::{storage::Storage, SingleSlotQueue, EMPTY, FULL};
+use atomic_polyfill::Ordering;
+use core::ptr;
+
+/// Single slot queue, with the interface of `heapless::spsc::Queue`.
+pub struct Queue<T, const N: usize> {
+    ssq: SingleSlotQueue<T>,
+}
+
+impl<T, const N: usize> Queue<T, N> {
+    const CHECK_N: () = assert!(
+        N == 2,
+        "an `ssq::spsc::Queue` holds a single value, so N must be 2"
+    );
+
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        let () = Self::CHECK_N;
+        Queue {
+            ssq: SingleSlotQueue::new(),
+        }
+    }
+
+    /// Split the queue into its producer and consumer. Note that the order is the opposite of
+    /// [`SingleSlotQueue::split`].
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        let (cons, prod) = self.ssq.split();
+        (Producer { prod }, Consumer { cons })
+    }
+
+    /// The number of values the queue can hold, `N - 1`, which is always 1.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+
+    /// The number of values in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        usize::from(self.is_full())
+    }
+
+    /// Check if there is no value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.is_full()
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.ssq.state.load(Ordering::Relaxed) == FULL
+    }
+
+    /// Write a value into the queue, or return it if the queue is full.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Result<(), T> {
+        let state = self.ssq.state.get_mut();
+        if *state == FULL {
+            return Err(val);
+        }
+
+        unsafe { ptr::write(self.ssq.val.as_ptr(), val) };
+        *self.ssq.state.get_mut() = FULL;
+        Ok(())
+    }
+
+    /// Take the value out of the queue, if any.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        let state = self.ssq.state.get_mut();
+        if *state != FULL {
+            return None;
+        }
+
+        *state = EMPTY;
+        Some(unsafe { ptr::read(self.ssq.val.as_ptr()) })
+    }
+
+    /// Borrow the value in the queue, if any.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        // Safety: the handles borrow the queue mutably, so none of them can be accessing the
+        // slot while we hold a shared reference to it.
+        self.is_full().then(|| unsafe { &*self.ssq.val.as_ptr() })
+    }
+}
+
+/// Write handle to a queue, with the interface of `heapless::spsc::Producer`.
+pub struct Producer<'a, T, const N: usize> {
+    prod: crate::Producer<'a, T>,
+}
+
+impl<'a, T, const N: usize> Producer<'a, T, N> {
+    /// Write a value into the queue, or return it if it could not be written.
+    ///
+    /// Besides when the queue is full, the value is also returned if the corresponding
+    /// [`Consumer`] is in the middle of dequeuing, or has been dropped.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Result<(), T> {
+        self.prod.try_enqueue(val).map_err(|e| e.into_inner())
+    }
+
+    /// Write a value into the queue without checking that it is empty.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`crate::Producer::enqueue_unchecked`].
+    #[inline]
+    pub unsafe fn enqueue_unchecked(&mut self, val: T) {
+        self.prod.enqueue_unchecked(val)
+    }
+
+    /// Check if there is room in the queue for a value.
+    #[inline]
+    pub fn ready(&self) -> bool {
+        self.prod.is_empty()
+    }
+
+    /// The number of values in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        usize::from(self.prod.is_full())
+    }
+
+    /// Check if there is no value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.prod.is_empty()
+    }
+
+    /// The number of values the queue can hold, `N - 1`, which is always 1.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+}
+
+/// Read handle to a queue, with the interface of `heapless::spsc::Consumer`.
+pub struct Consumer<'a, T, const N: usize> {
+    cons: crate::Consumer<'a, T>,
+}
+
+impl<'a, T, const N: usize> Consumer<'a, T, N> {
+    /// Take the value out of the queue, if any.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.cons.dequeue()
+    }
+
+    /// Take the value out of the queue without checking that there is one.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`crate::Consumer::dequeue_unchecked`].
+    #[inline]
+    pub unsafe fn dequeue_unchecked(&mut self) -> T {
+        self.cons.dequeue_unchecked()
+    }
+
+    /// Borrow the value in the queue, if any.
+    #[inline]
+    pub fn peek(&self) -> Option<&T> {
+        let ssq = self.cons.ssq;
+        // Safety: the producer of this wrapper never overwrites or takes back a value, so the
+        // value stays put until it is dequeued, which needs a mutable borrow of `self`.
+        (ssq.state.load(Ordering::Acquire) == FULL).then(|| unsafe { &*ssq.val.as_ptr() })
+    }
+
+    /// Check if there is a value to dequeue.
+    #[inline]
+    pub fn ready(&self) -> bool {
+        self.cons.is_full()
+    }
+
+    /// The number of values in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        usize::from(self.cons.is_full())
+    }
+
+    /// Check if there is no value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// The number of values the queue can hold, `N - 1`, which is always 1.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N - 1
+    }
+}
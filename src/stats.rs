@@ -0,0 +1,137 @@
+//! Traffic counters for a [`SingleSlotQueue`](crate::SingleSlotQueue), enabled by the `stats`
+//! feature.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::SingleSlotQueue;
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! let (mut cons, mut prod) = queue.split();
+//!
+//! prod.enqueue(1);
+//! prod.enqueue(2);
+//! prod.enqueue_overwrite(3);
+//! cons.dequeue();
+//!
+//! let stats = cons.stats();
+//! assert!(stats.enqueues == 2);
+//! assert!(stats.rejected == 1);
+//! assert!(stats.overwrites == 1);
+//! assert!(stats.dequeues == 1);
+//! ```
+
+use atomic_polyfill::{AtomicUsize, Ordering};
+
+/// Snapshot of the counters of a queue, returned by
+/// [`Consumer::stats`](crate::Consumer::stats) and [`Producer::stats`](crate::Producer::stats).
+///
+/// All counters wrap around on overflow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Stats {
+    /// Number of values written into the queue, including those that overwrote an unread value.
+    pub enqueues: usize,
+    /// Number of values dequeued by the consumer.
+    pub dequeues: usize,
+    /// Number of unread values that were displaced by a newer one.
+    pub overwrites: usize,
+    /// Number of values handed back to the producer because they could not be written.
+    pub rejected: usize,
+}
+
+/// Snapshot of the contention counters of a queue, enabled by the `contention` feature and
+/// returned by [`Consumer::contention`](crate::Consumer::contention) and
+/// [`Producer::contention`](crate::Producer::contention).
+///
+/// The queue never spins waiting for the slot: a handle that finds the other one accessing the
+/// slot gives up right away, and a producer retries taking the slot at most once, when the
+/// consumer dequeued the value it was about to overwrite. So on a setup where the handles never
+/// contend, both counters stay at 0. All counters wrap around on overflow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+#[cfg(feature = "contention")]
+pub struct Contention {
+    /// Number of times a handle found the slot held by the other handle, and gave up.
+    pub busy: usize,
+    /// Number of times the producer had to retry taking the slot, because the consumer changed
+    /// its state in the meantime.
+    pub retries: usize,
+}
+
+/// Live counters, embedded in the queue.
+pub(crate) struct Counters {
+    enqueues: AtomicUsize,
+    dequeues: AtomicUsize,
+    overwrites: AtomicUsize,
+    rejected: AtomicUsize,
+    #[cfg(feature = "contention")]
+    busy: AtomicUsize,
+    #[cfg(feature = "contention")]
+    retries: AtomicUsize,
+}
+
+impl Counters {
+    pub(crate) const fn new() -> Self {
+        Counters {
+            enqueues: AtomicUsize::new(0),
+            dequeues: AtomicUsize::new(0),
+            overwrites: AtomicUsize::new(0),
+            rejected: AtomicUsize::new(0),
+            #[cfg(feature = "contention")]
+            busy: AtomicUsize::new(0),
+            #[cfg(feature = "contention")]
+            retries: AtomicUsize::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn enqueued(&self) {
+        self.enqueues.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn dequeued(&self) {
+        self.dequeues.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn overwrote(&self) {
+        self.overwrites.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn rejected(&self) {
+        self.rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "contention")]
+    #[inline]
+    pub(crate) fn busy(&self) {
+        self.busy.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "contention")]
+    #[inline]
+    pub(crate) fn retried(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> Stats {
+        Stats {
+            enqueues: self.enqueues.load(Ordering::Relaxed),
+            dequeues: self.dequeues.load(Ordering::Relaxed),
+            overwrites: self.overwrites.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    #[cfg(feature = "contention")]
+    pub(crate) fn contention(&self) -> Contention {
+        Contention {
+            busy: self.busy.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Consumer adapter which remembers the last value it dequeued, for "current setpoint" use
+//! cases where the latest value matters more than whether it is new.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::{sticky::Sticky, SingleSlotQueue};
+//! let mut queue = SingleSlotQueue::<u32>::new();
+//! let (cons, mut prod) = queue.split();
+//! let mut cons = Sticky::new(cons);
+//!
+//! assert!(cons.latest() == None);
+//!
+//! prod.enqueue(10);
+//! assert!(cons.latest() == Some(&10));
+//!
+//! // The setpoint stays around after it has been read.
+//! assert!(cons.latest() == Some(&10));
+//! assert!(cons.peek() == Some(10));
+//! ```
+
+use crate::{
+    storage::{Inline, Storage},
+    Consumer,
+};
+
+/// Consumer which keeps a copy of the last value dequeued from the queue.
+pub struct Sticky<'a, T, S: Storage<T> = Inline<T>> {
+    cons: Consumer<'a, T, S>,
+    last: Option<T>,
+}
+
+impl<'a, T, S: Storage<T>> Sticky<'a, T, S> {
+    #[inline]
+    pub fn new(cons: Consumer<'a, T, S>) -> Self {
+        Sticky { cons, last: None }
+    }
+
+    /// Get the last value published, dequeuing it first if it is new.
+    ///
+    /// Returns `None` only if no value was ever dequeued.
+    #[inline]
+    pub fn latest(&mut self) -> Option<&T> {
+        if let Some(v) = self.cons.dequeue() {
+            self.last = Some(v);
+        }
+        self.last.as_ref()
+    }
+
+    /// Check if a value was published since the last one was dequeued.
+    #[inline]
+    pub fn is_updated(&self) -> bool {
+        !self.cons.is_empty()
+    }
+
+    /// Give back the wrapped consumer, along with the last value dequeued.
+    #[inline]
+    pub fn into_inner(self) -> (Consumer<'a, T, S>, Option<T>) {
+        (self.cons, self.last)
+    }
+}
+
+impl<'a, T: Clone, S: Storage<T>> Sticky<'a, T, S> {
+    /// Try reading a new value from the queue, leaving a copy behind for
+    /// [`latest`](Sticky::latest).
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        let v = self.cons.dequeue()?;
+        self.last = Some(v.clone());
+        Some(v)
+    }
+
+    /// Get a copy of the last value published, dequeuing it first if it is new.
+    #[inline]
+    pub fn peek(&mut self) -> Option<T> {
+        self.latest().cloned()
+    }
+}
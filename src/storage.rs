@@ -0,0 +1,66 @@
+//! Backing storage for the value of a [`SingleSlotQueue`](crate::SingleSlotQueue).
+
+use core::{cell::UnsafeCell, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+/// Memory holding the value of a [`SingleSlotQueue`](crate::SingleSlotQueue).
+///
+/// # Safety
+///
+/// [`as_ptr`](Storage::as_ptr) must always return the same pointer, valid for reads and writes of
+/// a `T` for as long as the storage is alive, and not accessed by anything but the queue.
+///
+/// The queue handles are only [`Send`] if the storage is [`Sync`], as both of them access it
+/// through a shared reference.
+pub unsafe trait Storage<T> {
+    /// Pointer to the (possibly uninitialized) value.
+    fn as_ptr(&self) -> *mut T;
+}
+
+/// Storage held inline in the queue. This is the default.
+pub struct Inline<T>(UnsafeCell<MaybeUninit<T>>);
+
+impl<T> Inline<T> {
+    pub(crate) const fn new() -> Self {
+        Inline(UnsafeCell::new(MaybeUninit::uninit()))
+    }
+
+    pub(crate) const fn with_value(val: T) -> Self {
+        Inline(UnsafeCell::new(MaybeUninit::new(val)))
+    }
+}
+
+/// Safety: The queue gates every access to the value with its state.
+unsafe impl<T: Send> Sync for Inline<T> {}
+
+unsafe impl<T> Storage<T> for Inline<T> {
+    #[inline]
+    fn as_ptr(&self) -> *mut T {
+        self.0.get().cast()
+    }
+}
+
+/// Storage provided by the caller, e.g. placed in a specific RAM bank or linker section. Created
+/// with [`SingleSlotQueue::from_storage`](crate::SingleSlotQueue::from_storage).
+pub struct Borrowed<'s, T> {
+    ptr: NonNull<T>,
+    _marker: PhantomData<&'s mut MaybeUninit<T>>,
+}
+
+impl<'s, T> Borrowed<'s, T> {
+    pub(crate) fn new(storage: &'s mut MaybeUninit<T>) -> Self {
+        Borrowed {
+            ptr: NonNull::from(storage).cast(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Safety: The queue gates every access to the value with its state.
+unsafe impl<'s, T: Send> Sync for Borrowed<'s, T> {}
+
+unsafe impl<'s, T> Storage<T> for Borrowed<'s, T> {
+    #[inline]
+    fn as_ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
@@ -0,0 +1,187 @@
+//! Single slot queue stamping every value with the time it was enqueued, so consumers can
+//! reason about the age of the data.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::timestamp::{Clock, TimestampedSlot};
+//! use std::time::Instant;
+//!
+//! struct StdClock;
+//! impl Clock for StdClock {
+//!     type Instant = Instant;
+//!     fn now(&self) -> Instant {
+//!         Instant::now()
+//!     }
+//! }
+//!
+//! let mut slot = TimestampedSlot::<u32, _>::new(StdClock);
+//! let (mut cons, mut prod) = slot.split();
+//!
+//! let before = Instant::now();
+//! assert!(prod.enqueue(1) == None);
+//!
+//! let (val, stamp) = cons.dequeue().unwrap();
+//! assert!(val == 1);
+//! assert!(stamp >= before);
+//! ```
+
+use crate::{Consumer as SsqConsumer, Overwrite, Producer as SsqProducer, SingleSlotQueue};
+use core::ops::Sub;
+
+/// Source of timestamps.
+///
+/// The staleness methods of the [`Consumer`] are available when subtracting two instants gives
+/// a comparable duration, as is the case for `std`, `embassy-time` and `fugit` instants.
+pub trait Clock {
+    type Instant: Copy;
+
+    /// The current instant.
+    fn now(&self) -> Self::Instant;
+
+    /// The time elapsed from `earlier` to `later`.
+    ///
+    /// This subtracts the instants by default, which is only correct for clocks that never wrap
+    /// around, such as `std` and `embassy-time` instants. Clocks reading a hardware tick counter
+    /// that wraps around must override it with a wrapping subtraction (e.g.
+    /// [`u32::wrapping_sub`]), or the subtraction overflows once the counter wraps.
+    #[inline]
+    fn duration_since(
+        &self,
+        later: Self::Instant,
+        earlier: Self::Instant,
+    ) -> <Self::Instant as Sub>::Output
+    where
+        Self::Instant: Sub,
+    {
+        later - earlier
+    }
+}
+
+/// Single slot queue holding a value along with the instant it was enqueued.
+pub struct TimestampedSlot<T, C: Clock> {
+    ssq: SingleSlotQueue<(T, C::Instant)>,
+    clock: C,
+}
+
+impl<T, C: Clock> TimestampedSlot<T, C> {
+    /// Create an empty queue, stamping values with `clock`.
+    pub const fn new(clock: C) -> Self {
+        TimestampedSlot {
+            ssq: SingleSlotQueue::new(),
+            clock,
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T, C>, Producer<'_, T, C>) {
+        let (cons, prod) = self.ssq.split();
+        (
+            Consumer {
+                cons,
+                clock: &self.clock,
+            },
+            Producer {
+                prod,
+                clock: &self.clock,
+            },
+        )
+    }
+}
+
+/// Read handle to a timestamped slot.
+pub struct Consumer<'a, T, C: Clock> {
+    cons: SsqConsumer<'a, (T, C::Instant)>,
+    clock: &'a C,
+}
+
+impl<'a, T, C: Clock> Consumer<'a, T, C> {
+    /// Try reading a value from the queue, along with the instant it was enqueued.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<(T, C::Instant)> {
+        self.cons.dequeue()
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cons.is_empty()
+    }
+
+    /// The clock values are stamped with.
+    #[inline]
+    pub fn clock(&self) -> &C {
+        self.clock
+    }
+}
+
+impl<'a, T, C, D> Consumer<'a, T, C>
+where
+    C: Clock,
+    C::Instant: Sub<Output = D>,
+    D: PartialOrd,
+{
+    /// The time elapsed since the value in the queue was enqueued, if there is one.
+    #[inline]
+    pub fn age(&mut self) -> Option<D> {
+        let stamp = self.cons.peek_with(|(_, stamp)| *stamp)?;
+        Some(self.clock.duration_since(self.clock.now(), stamp))
+    }
+
+    /// Try reading a value from the queue, as long as it was enqueued no longer than `max_age`
+    /// ago.
+    ///
+    /// Stale values are dequeued and dropped, so that the next value to come in is not held
+    /// back by them.
+    pub fn dequeue_fresh(&mut self, max_age: D) -> Option<(T, C::Instant)> {
+        let (val, stamp) = self.cons.dequeue()?;
+        if self.clock.duration_since(self.clock.now(), stamp) > max_age {
+            None
+        } else {
+            Some((val, stamp))
+        }
+    }
+}
+
+impl<'a, T: Copy, C: Clock> Consumer<'a, T, C> {
+    /// Try reading a value along with the instant it was enqueued, without dequeuing.
+    #[inline]
+    pub fn peek(&mut self) -> Option<(T, C::Instant)> {
+        self.cons.peek()
+    }
+}
+
+/// Write handle to a timestamped slot.
+pub struct Producer<'a, T, C: Clock> {
+    prod: SsqProducer<'a, (T, C::Instant)>,
+    clock: &'a C,
+}
+
+impl<'a, T, C: Clock> Producer<'a, T, C> {
+    /// Write a value into the queue, stamped with the current instant, as with
+    /// [`Producer::enqueue`](crate::Producer::enqueue).
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        let now = self.clock.now();
+        self.prod.enqueue((val, now)).map(|(v, _)| v)
+    }
+
+    /// Write a value into the queue, stamped with the current instant, as with
+    /// [`Producer::enqueue_overwrite`](crate::Producer::enqueue_overwrite).
+    #[inline]
+    pub fn enqueue_overwrite(&mut self, val: T) -> Overwrite<T> {
+        let now = self.clock.now();
+        self.prod.enqueue_overwrite((val, now)).map(|(v, _)| v)
+    }
+
+    /// Check if there is a value in the queue.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.prod.is_empty()
+    }
+
+    /// The clock values are stamped with.
+    #[inline]
+    pub fn clock(&self) -> &C {
+        self.clock
+    }
+}
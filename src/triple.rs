@@ -0,0 +1,140 @@
+//! Wait-free triple buffer, for tear-free exchange of large values.
+//!
+//! The producer and the consumer each own one of three buffers, and the third one is handed
+//! between them with a single atomic swap. Neither side ever blocks, spins, or fails: the
+//! producer can always publish, and the consumer can always read the most recently published
+//! value.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::triple::TripleBuffer;
+//! let mut buf = TripleBuffer::new([0u8; 16]);
+//! let (mut cons, mut prod) = buf.split();
+//!
+//! // Values can be built in place, then published.
+//! prod.input_buffer()[0] = 1;
+//! prod.publish();
+//! assert!(cons.read()[0] == 1);
+//!
+//! // Reading again returns the same value until a new one is published.
+//! prod.write([2; 16]);
+//! prod.write([3; 16]);
+//! assert!(cons.read() == &[3; 16]);
+//! assert!(cons.read() == &[3; 16]);
+//! ```
+
+use atomic_polyfill::{AtomicU8, Ordering};
+use core::cell::UnsafeCell;
+
+/// Mask of the buffer index in the `back` state.
+const INDEX: u8 = 0b011;
+/// Set in the `back` state when the back buffer holds a value the consumer has not read yet.
+const DIRTY: u8 = 0b100;
+
+/// Triple buffer holding the latest value written by its [`Producer`].
+pub struct TripleBuffer<T> {
+    bufs: [UnsafeCell<T>; 3],
+    /// Index of the buffer between the two handles, along with the [`DIRTY`] flag.
+    back: AtomicU8,
+    /// Index of the buffer owned by the [`Producer`]. Only accessed through it.
+    input: AtomicU8,
+    /// Index of the buffer owned by the [`Consumer`]. Only accessed through it.
+    output: AtomicU8,
+}
+
+impl<T> TripleBuffer<T> {
+    /// Create a triple buffer whose consumer reads `init` until a value is published.
+    pub fn new(init: T) -> Self
+    where
+        T: Clone,
+    {
+        TripleBuffer {
+            bufs: [
+                UnsafeCell::new(init.clone()),
+                UnsafeCell::new(init.clone()),
+                UnsafeCell::new(init),
+            ],
+            back: AtomicU8::new(0),
+            input: AtomicU8::new(1),
+            output: AtomicU8::new(2),
+        }
+    }
+
+    pub fn split(&mut self) -> (Consumer<'_, T>, Producer<'_, T>) {
+        (Consumer { buf: self }, Producer { buf: self })
+    }
+
+    #[inline]
+    fn get(&self, idx: &AtomicU8) -> *mut T {
+        self.bufs[usize::from(idx.load(Ordering::Relaxed))].get()
+    }
+}
+
+/// Read handle to a triple buffer.
+pub struct Consumer<'a, T> {
+    buf: &'a TripleBuffer<T>,
+}
+
+impl<'a, T> Consumer<'a, T> {
+    /// Check if a value has been published since the last [`read`](Consumer::read).
+    #[inline]
+    pub fn is_updated(&self) -> bool {
+        self.buf.back.load(Ordering::Relaxed) & DIRTY != 0
+    }
+
+    /// Get the most recently published value.
+    ///
+    /// This method never blocks. If no value has been published since the last call, the same
+    /// value is returned again.
+    #[inline]
+    pub fn read(&mut self) -> &T {
+        if self.is_updated() {
+            let output = self.buf.output.load(Ordering::Relaxed);
+            let back = self.buf.back.swap(output, Ordering::AcqRel);
+            self.buf.output.store(back & INDEX, Ordering::Relaxed);
+        }
+
+        // SAFETY: The output buffer is owned by the consumer until it hands it back.
+        unsafe { &*self.buf.get(&self.buf.output) }
+    }
+}
+
+/// Safety: Each buffer is only ever accessed by the handle that owns it.
+unsafe impl<'a, T: Send> Send for Consumer<'a, T> {}
+
+/// Write handle to a triple buffer.
+pub struct Producer<'a, T> {
+    buf: &'a TripleBuffer<T>,
+}
+
+impl<'a, T> Producer<'a, T> {
+    /// Get the buffer the next value is built in, before being [`publish`](Producer::publish)ed.
+    ///
+    /// The buffer holds an older value, which is not necessarily the last one published.
+    #[inline]
+    pub fn input_buffer(&mut self) -> &mut T {
+        // SAFETY: The input buffer is owned by the producer until it publishes it.
+        unsafe { &mut *self.buf.get(&self.buf.input) }
+    }
+
+    /// Publish the input buffer, making it the value returned by [`Consumer::read`].
+    ///
+    /// This method never blocks.
+    #[inline]
+    pub fn publish(&mut self) {
+        let input = self.buf.input.load(Ordering::Relaxed);
+        let back = self.buf.back.swap(input | DIRTY, Ordering::AcqRel);
+        self.buf.input.store(back & INDEX, Ordering::Relaxed);
+    }
+
+    /// Write a value into the input buffer and publish it.
+    #[inline]
+    pub fn write(&mut self, val: T) {
+        *self.input_buffer() = val;
+        self.publish();
+    }
+}
+
+/// Safety: Each buffer is only ever accessed by the handle that owns it.
+unsafe impl<'a, T: Send> Send for Producer<'a, T> {}
@@ -0,0 +1,106 @@
+//! Storage for the waker of a task waiting on the other end of the queue.
+//!
+//! This is an `AtomicWaker`-style cell: registering and waking never take a lock or a critical
+//! section, so the waking side may run in an interrupt handler while the registering side runs
+//! on any executor (or vice versa).
+
+use atomic_polyfill::{AtomicU8, Ordering};
+use core::{cell::UnsafeCell, task::Waker};
+
+/// No one is accessing the stored waker.
+const WAITING: u8 = 0;
+/// A task is registering a new waker.
+const REGISTERING: u8 = 0b01;
+/// The waker is being taken out to be woken.
+const WAKING: u8 = 0b10;
+
+/// A single registered [`Waker`], shared between the two handles.
+pub(crate) struct WakerCell {
+    state: AtomicU8,
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+/// Safety: Access to the `UnsafeCell` is gated by the `AtomicU8`, and `Waker` is `Send + Sync`.
+unsafe impl Sync for WakerCell {}
+
+impl WakerCell {
+    pub(crate) const fn new() -> Self {
+        WakerCell {
+            state: AtomicU8::new(WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Register `waker` to be woken by the next call to [`wake`](WakerCell::wake), replacing
+    /// any previously registered waker.
+    ///
+    /// If a concurrent call to `wake` is in progress, `waker` is woken immediately instead.
+    pub(crate) fn register(&self, waker: &Waker) {
+        match self
+            .state
+            .compare_exchange(WAITING, REGISTERING, Ordering::Acquire, Ordering::Acquire)
+            .unwrap_or_else(|s| s)
+        {
+            WAITING => {
+                // SAFETY: the REGISTERING bit gives us exclusive access to the stored waker;
+                // `take` only touches it after observing that no one was REGISTERING.
+                unsafe {
+                    match &*self.waker.get() {
+                        Some(w) if w.will_wake(waker) => {}
+                        _ => *self.waker.get() = Some(waker.clone()),
+                    }
+                }
+
+                if self
+                    .state
+                    .compare_exchange(REGISTERING, WAITING, Ordering::AcqRel, Ordering::Acquire)
+                    .is_err()
+                {
+                    // `wake` was called while we were registering and could not take the
+                    // waker, so it is up to us to do it. The state is REGISTERING | WAKING.
+                    let w = unsafe { (*self.waker.get()).take() };
+                    self.state.swap(WAITING, Ordering::AcqRel);
+                    if let Some(w) = w {
+                        w.wake();
+                    }
+                }
+            }
+            WAKING => {
+                // `wake` is currently taking the old waker out; make sure this one doesn't
+                // miss the notification.
+                waker.wake_by_ref();
+            }
+            // Registration only ever happens from one handle at a time, so the cell can't be
+            // REGISTERING here.
+            _ => {}
+        }
+    }
+
+    /// Wake the registered waker, if any.
+    pub(crate) fn wake(&self) {
+        if let Some(w) = self.take() {
+            w.wake();
+        }
+    }
+
+    /// Remove the registered waker, if any, without waking it.
+    pub(crate) fn clear(&self) {
+        drop(self.take());
+    }
+
+    /// Take the registered waker out of the cell, if any.
+    fn take(&self) -> Option<Waker> {
+        match self.state.fetch_or(WAKING, Ordering::AcqRel) {
+            WAITING => {
+                // SAFETY: the WAKING bit gives us exclusive access to the stored waker;
+                // `register` won't touch it until the bit is cleared.
+                let w = unsafe { (*self.waker.get()).take() };
+                self.state.fetch_and(!WAKING, Ordering::Release);
+                w
+            }
+            // Either a concurrent `register` will see the WAKING bit and wake its waker
+            // itself, or a concurrent `take` is already doing the waking.
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,262 @@
+//! Latest-value channel observed by a fixed number of consumers.
+//!
+//! Each consumer keeps track of the last version it has seen, so several tasks can each react to
+//! a new value (e.g. a sensor reading) at their own pace, without taking it away from the
+//! others.
+//!
+//! The value is guarded by a sequence lock: the producer never waits for the consumers, and the
+//! consumers never wait for each other. A consumer copies the value out, and retries if the
+//! producer replaced it in the meantime.
+//!
+//! # Example
+//!
+//! ```
+//! use ssq::watch::Watch;
+//! let mut watch = Watch::<u32, 2>::new();
+//! let ([mut a, mut b], mut prod) = watch.split();
+//!
+//! assert!(a.get_changed() == None);
+//!
+//! prod.enqueue(1);
+//! assert!(a.get_changed() == Some(1));
+//! assert!(a.get_changed() == None);
+//!
+//! // Every consumer sees the new value.
+//! assert!(b.get_changed() == Some(1));
+//! assert!(b.get() == Some(1));
+//! ```
+
+use atomic_polyfill::{fence, AtomicBool, AtomicUsize, Ordering};
+use core::{cell::UnsafeCell, mem::MaybeUninit, ptr};
+#[cfg(feature = "async")]
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "async")]
+use crate::{waker::WakerCell, Closed};
+
+/// Latest-value channel with one producer and `N` consumers.
+pub struct Watch<T, const N: usize> {
+    val: UnsafeCell<MaybeUninit<T>>,
+    /// Incremented when the producer starts writing a value, and again once it is published, so
+    /// it is odd while a value is being written, and 0 until the first one is published.
+    version: AtomicUsize,
+    /// Set when the [`Producer`] is dropped.
+    tx_closed: AtomicBool,
+    /// One waker per consumer.
+    #[cfg(feature = "async")]
+    rx_wakers: [WakerCell; N],
+}
+
+impl<T, const N: usize> Watch<T, N> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Watch {
+            val: UnsafeCell::new(MaybeUninit::uninit()),
+            version: AtomicUsize::new(0),
+            tx_closed: AtomicBool::new(false),
+            #[cfg(feature = "async")]
+            rx_wakers: [const { WakerCell::new() }; N],
+        }
+    }
+
+    /// Split the channel into its `N` consumers and its producer.
+    ///
+    /// Every consumer starts out having seen the current value, if any.
+    pub fn split(&mut self) -> ([Consumer<'_, T, N>; N], Producer<'_, T, N>) {
+        *self.tx_closed.get_mut() = false;
+        let seen = *self.version.get_mut();
+        let this = &*self;
+        let cons = core::array::from_fn(|_id| Consumer {
+            watch: this,
+            seen,
+            #[cfg(feature = "async")]
+            id: _id,
+        });
+        (cons, Producer { watch: this })
+    }
+}
+
+impl<T, const N: usize> Drop for Watch<T, N> {
+    fn drop(&mut self) {
+        if *self.version.get_mut() != 0 {
+            unsafe { self.val.get_mut().assume_init_drop() };
+        }
+    }
+}
+
+/// Safety: The producer and the consumers borrow the channel, and only the producer writes the
+/// value.
+unsafe impl<T: Send, const N: usize> Sync for Watch<T, N> {}
+
+/// Read handle to a watch channel.
+pub struct Consumer<'a, T, const N: usize> {
+    watch: &'a Watch<T, N>,
+    /// Last version returned by this consumer.
+    seen: usize,
+    /// Index of this consumer's waker.
+    #[cfg(feature = "async")]
+    id: usize,
+}
+
+impl<'a, T, const N: usize> Consumer<'a, T, N> {
+    /// Check if a value has been published since this consumer last read one.
+    #[inline]
+    pub fn has_changed(&self) -> bool {
+        self.watch.version.load(Ordering::Acquire) != self.seen
+    }
+
+    /// Check if the [`Producer`] has been dropped.
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.watch.tx_closed.load(Ordering::Acquire)
+    }
+
+    /// Wait for a value to be published since this consumer last read one.
+    ///
+    /// Once the [`Producer`] has been dropped and there is no new value, the future resolves to
+    /// `Err(Closed)`.
+    #[cfg(feature = "async")]
+    #[inline]
+    pub fn changed(&mut self) -> ChangedFuture<'_, 'a, T, N> {
+        ChangedFuture { cons: self }
+    }
+
+    /// Poll for a new value, for use in hand-written futures.
+    #[cfg(feature = "async")]
+    pub fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        if self.has_changed() {
+            return Poll::Ready(Ok(()));
+        }
+
+        self.watch.rx_wakers[self.id].register(cx.waker());
+
+        // Check again, in case the producer published a value before the waker was registered.
+        if self.has_changed() {
+            return Poll::Ready(Ok(()));
+        }
+
+        if self.is_closed() {
+            // The producer may have published a final value right before closing.
+            return Poll::Ready(if self.has_changed() {
+                Ok(())
+            } else {
+                Err(Closed)
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<'a, T: Copy, const N: usize> Consumer<'a, T, N> {
+    /// Read the current value, marking it as seen.
+    ///
+    /// Other consumers do not get in the way, and the read is retried if the [`Producer`]
+    /// replaces the value while it is being copied. Since the copy may be torn and thrown away,
+    /// `T` must be [`Copy`]. This method never blocks: if nothing has been published yet, or the
+    /// producer is in the middle of writing a value (e.g. because it was preempted by this
+    /// consumer), `None` is returned.
+    #[inline]
+    pub fn get(&mut self) -> Option<T> {
+        loop {
+            let version = self.watch.version.load(Ordering::Acquire);
+            if version == 0 || version % 2 == 1 {
+                return None;
+            }
+
+            let val = unsafe { ptr::read_volatile(self.watch.val.get()) };
+            fence(Ordering::Acquire);
+            if self.watch.version.load(Ordering::Relaxed) == version {
+                self.seen = version;
+                // Safety: the producer did not touch the value while it was being copied.
+                return Some(unsafe { val.assume_init() });
+            }
+        }
+    }
+
+    /// Read the current value only if it has not been seen by this consumer yet.
+    #[inline]
+    pub fn get_changed(&mut self) -> Option<T> {
+        if self.has_changed() {
+            self.get()
+        } else {
+            None
+        }
+    }
+}
+
+/// Safety: Consumers only ever copy the value out, and discard copies that raced with a write.
+unsafe impl<'a, T: Send, const N: usize> Send for Consumer<'a, T, N> {}
+
+/// Future returned by [`Consumer::changed`].
+///
+/// Dropping the future before it completes deregisters its waker from the channel.
+#[cfg(feature = "async")]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct ChangedFuture<'b, 'a, T, const N: usize> {
+    cons: &'b mut Consumer<'a, T, N>,
+}
+
+#[cfg(feature = "async")]
+impl<'b, 'a, T, const N: usize> Future for ChangedFuture<'b, 'a, T, N> {
+    type Output = Result<(), Closed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Closed>> {
+        self.get_mut().cons.poll_changed(cx)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'b, 'a, T, const N: usize> Drop for ChangedFuture<'b, 'a, T, N> {
+    fn drop(&mut self) {
+        self.cons.watch.rx_wakers[self.cons.id].clear();
+    }
+}
+
+/// Write handle to a watch channel.
+pub struct Producer<'a, T, const N: usize> {
+    watch: &'a Watch<T, N>,
+}
+
+impl<'a, T, const N: usize> Producer<'a, T, N> {
+    /// Publish a value, replacing the current one, and return the old value, if any.
+    ///
+    /// This never waits for the consumers, which retry their read if it overlaps with the write.
+    #[inline]
+    pub fn enqueue(&mut self, val: T) -> Option<T> {
+        let watch = self.watch;
+        let version = watch.version.load(Ordering::Relaxed);
+        // Skip 0 when wrapping around, as it means nothing was published yet.
+        let next = match version.wrapping_add(2) {
+            0 => 2,
+            v => v,
+        };
+        watch.version.store(next - 1, Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        let ptr = watch.val.get();
+        // Safety: only the producer writes the value, and consumers only ever copy it out.
+        let old = (version != 0).then(|| unsafe { ptr::read(ptr).assume_init() });
+        unsafe { ptr::write_volatile(ptr, MaybeUninit::new(val)) };
+
+        watch.version.store(next, Ordering::Release);
+        #[cfg(feature = "async")]
+        watch.rx_wakers.iter().for_each(WakerCell::wake);
+        old
+    }
+}
+
+impl<'a, T, const N: usize> Drop for Producer<'a, T, N> {
+    fn drop(&mut self) {
+        self.watch.tx_closed.store(true, Ordering::Release);
+        #[cfg(feature = "async")]
+        self.watch.rx_wakers.iter().for_each(WakerCell::wake);
+    }
+}
+
+/// Safety: There is only one producer, which is the only one ever writing the value.
+unsafe impl<'a, T: Send, const N: usize> Send for Producer<'a, T, N> {}
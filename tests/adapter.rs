@@ -0,0 +1,73 @@
+use ssq::{Overwrite, SingleSlotQueue};
+
+#[test]
+fn map() {
+    let mut queue = SingleSlotQueue::<u8>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = cons.map_values(u32::from);
+
+    assert!(cons.dequeue().is_none());
+    prod.enqueue(7);
+    assert!(!cons.is_empty());
+    assert!(cons.peek() == Some(7u32));
+    assert!(cons.dequeue() == Some(7u32));
+
+    drop(prod);
+    assert!(cons.is_closed());
+    assert!(cons.into_inner().dequeue().is_none());
+}
+
+#[test]
+fn filter() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = cons.filter_values(|&v| v % 2 == 0);
+
+    prod.enqueue(1);
+    assert!(cons.dequeue().is_none());
+    // The value that did not match was discarded.
+    assert!(cons.is_empty());
+
+    prod.enqueue(2);
+    assert!(cons.dequeue() == Some(2));
+    assert!(!cons.is_closed());
+}
+
+#[test]
+fn inspect() {
+    let mut seen = Vec::new();
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = cons.inspect_values(|&v| seen.push(v));
+
+    assert!(cons.dequeue().is_none());
+    prod.enqueue(1);
+    assert!(cons.dequeue() == Some(1));
+    prod.enqueue(2);
+    assert!(cons.dequeue() == Some(2));
+    drop(cons);
+
+    assert!(seen == [1, 2]);
+}
+
+#[test]
+fn contramap() {
+    enum Event {
+        Pressed,
+        Released,
+    }
+
+    let mut queue = SingleSlotQueue::<u16>::new();
+    let (mut cons, prod) = queue.split();
+    let mut prod = prod.contramap(|e: &Event| match e {
+        Event::Pressed => 1,
+        Event::Released => 2,
+    });
+
+    let event = Event::Pressed;
+    assert!(prod.enqueue(&event).is_none());
+    assert!(prod.enqueue(&Event::Released) == Some(2));
+    assert!(prod.enqueue_overwrite(&Event::Released) == Overwrite::Overwrote(1));
+    assert!(cons.dequeue() == Some(2));
+    assert!(prod.is_empty() && prod.is_consumer_connected());
+}
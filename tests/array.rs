@@ -0,0 +1,25 @@
+use ssq::array::SlotArray;
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn independent_slots() {
+    let mut slots = SlotArray::<u32, 4>::new();
+    let (mut cons, mut prod) = slots.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                while prod.enqueue(i as usize % 4, i).is_some() {}
+            }
+        });
+
+        let mut next = [0, 1, 2, 3];
+        while next.iter().any(|&n| n < 100) {
+            if let Some((idx, v)) = cons.dequeue_any() {
+                assert_eq!(v, next[idx]);
+                next[idx] += 4;
+            }
+        }
+    });
+}
@@ -0,0 +1,521 @@
+#![cfg(feature = "async")]
+#![cfg_attr(feature = "unstable", feature(async_iterator))]
+
+use futures::executor::block_on;
+use ssq::{Closed, Disconnected, Overwrite, SingleSlotQueue};
+use std::{thread, time::Duration};
+
+#[test]
+fn dequeue_async_ready() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(5);
+    assert!(block_on(cons.dequeue_async()) == Ok(5));
+}
+
+#[test]
+fn dequeue_async_wakes() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..10 {
+                thread::sleep(Duration::from_millis(1));
+                while prod.enqueue(i).is_some() {}
+            }
+        });
+
+        block_on(async {
+            for i in 0..10 {
+                assert!(cons.dequeue_async().await == Ok(i));
+            }
+        });
+    });
+}
+
+#[test]
+fn enqueue_async_waits_for_room() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..10 {
+                thread::sleep(Duration::from_millis(1));
+                loop {
+                    if let Some(v) = cons.dequeue() {
+                        assert!(v == i);
+                        break;
+                    }
+                }
+            }
+        });
+
+        block_on(async {
+            for i in 0..10 {
+                prod.enqueue_async(i).await.unwrap();
+            }
+        });
+    });
+}
+
+#[test]
+fn stream() {
+    use futures::StreamExt;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..10 {
+                while prod.enqueue(i).is_some() {}
+            }
+        });
+
+        let sum: u32 = block_on(
+            (&mut cons)
+                .take(10)
+                .fold(0, |acc, v| async move { acc + v }),
+        );
+        assert!(sum == (0..10).sum());
+    });
+}
+
+#[test]
+fn stream_adapters() {
+    use futures::StreamExt;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+
+    // The stream adapters are not shadowed by the consumer's own.
+    let mut doubled = cons.map(|v| v * 2);
+    prod.enqueue(1);
+    drop(prod);
+    assert!(block_on(doubled.next()) == Some(2));
+    assert!(block_on(doubled.next()).is_none());
+}
+
+#[test]
+fn sink_wait() {
+    use futures::SinkExt;
+    use ssq::future::FullBehavior;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, prod) = queue.split();
+    let mut sink = prod.into_sink(FullBehavior::Wait);
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..10 {
+                loop {
+                    if let Some(v) = cons.dequeue() {
+                        assert!(v == i);
+                        break;
+                    }
+                }
+            }
+        });
+
+        block_on(async {
+            for i in 0..10 {
+                sink.send(i).await.unwrap();
+            }
+        });
+    });
+}
+
+#[test]
+fn sink_overwrite() {
+    use futures::SinkExt;
+    use ssq::future::FullBehavior;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, prod) = queue.split();
+    let mut sink = prod.into_sink(FullBehavior::Overwrite);
+
+    block_on(async {
+        sink.send(1).await.unwrap();
+        sink.send(2).await.unwrap();
+    });
+    assert!(cons.dequeue() == Some(2));
+}
+
+#[test]
+fn sink_overwrite_wakes_after_peek() {
+    use futures::{
+        task::{waker, ArcWake},
+        SinkExt,
+    };
+    use ssq::future::FullBehavior;
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+
+    struct CountWakes(AtomicUsize);
+    impl ArcWake for CountWakes {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, prod) = queue.split();
+    let mut sink = prod.into_sink(FullBehavior::Overwrite);
+
+    let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+    let waker = waker(wakes.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    block_on(sink.send(1)).unwrap();
+    let guard = cons.peek_ref().unwrap();
+    {
+        let mut send = pin!(sink.send(2));
+        assert!(send.as_mut().poll(&mut cx) == Poll::Pending);
+
+        // The sink is woken once the consumer is done peeking, and can then overwrite the value.
+        drop(guard);
+        assert!(wakes.0.load(Ordering::Relaxed) == 1);
+        assert!(send.poll(&mut cx) == Poll::Ready(Ok(())));
+    }
+    assert!(cons.dequeue() == Some(2));
+}
+
+#[test]
+fn poll_api() {
+    use futures::task::noop_waker_ref;
+    use std::task::{Context, Poll};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    assert!(cons.poll_dequeue(&mut cx) == Poll::Pending);
+
+    let mut val = Some(1);
+    assert!(prod.poll_enqueue(&mut cx, &mut val) == Poll::Ready(Ok(())));
+    assert!(val.is_none());
+
+    let mut val = Some(2);
+    assert!(prod.poll_enqueue(&mut cx, &mut val) == Poll::Pending);
+    assert!(val == Some(2));
+
+    assert!(cons.poll_dequeue(&mut cx) == Poll::Ready(Ok(1)));
+    assert!(prod.poll_enqueue(&mut cx, &mut val) == Poll::Ready(Ok(())));
+    assert!(cons.poll_dequeue(&mut cx) == Poll::Ready(Ok(2)));
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn wake_from_other_thread() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..500 {
+                while prod.enqueue(i).is_some() {
+                    thread::yield_now();
+                }
+            }
+        });
+
+        block_on(async {
+            for i in 0..500 {
+                assert!(cons.dequeue_async().await == Ok(i));
+            }
+        });
+    });
+}
+
+#[test]
+fn dequeue_async_cancel_safe() {
+    use futures::{
+        future::{self, Either},
+        pin_mut,
+    };
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    block_on(async {
+        // The receive future loses the race, and is dropped while pending.
+        let dequeue = cons.dequeue_async();
+        let other = future::ready(());
+        pin_mut!(other);
+        match future::select(dequeue, other).await {
+            Either::Left(_) => panic!("nothing was enqueued"),
+            Either::Right(_) => {}
+        }
+
+        // A value enqueued after cancellation is still there for the next receive.
+        prod.enqueue(7);
+        assert!(cons.dequeue_async().await == Ok(7));
+    });
+}
+
+#[test]
+fn dequeue_async_drop_deregisters_waker() {
+    use futures::task::{waker, ArcWake};
+    use std::{
+        future::Future,
+        pin::pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+    };
+
+    struct CountWakes(AtomicUsize);
+    impl ArcWake for CountWakes {
+        fn wake_by_ref(arc_self: &Arc<Self>) {
+            arc_self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    let wakes = Arc::new(CountWakes(AtomicUsize::new(0)));
+    let waker = waker(wakes.clone());
+    let mut cx = Context::from_waker(&waker);
+
+    {
+        let dequeue = pin!(cons.dequeue_async());
+        assert!(dequeue.poll(&mut cx) == Poll::Pending);
+    }
+
+    prod.enqueue(1);
+    assert!(wakes.0.load(Ordering::Relaxed) == 0);
+    assert!(cons.dequeue() == Some(1));
+}
+
+#[test]
+fn close_on_producer_drop() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            thread::sleep(Duration::from_millis(1));
+            prod.enqueue(1);
+        });
+
+        block_on(async {
+            assert!(cons.dequeue_async().await == Ok(1));
+            assert!(cons.dequeue_async().await == Err(Closed));
+        });
+    });
+    assert!(cons.is_closed());
+}
+
+#[test]
+fn stream_ends_on_close() {
+    use futures::StreamExt;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+
+    prod.enqueue(3);
+    drop(prod);
+    assert!(block_on(cons.collect::<Vec<_>>()) == [3]);
+}
+
+#[test]
+fn enqueue_async_disconnect() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+
+    prod.enqueue(1);
+    assert!(prod.is_consumer_connected());
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            thread::sleep(Duration::from_millis(1));
+            drop(cons);
+        });
+
+        assert!(block_on(prod.enqueue_async(2)) == Err(Disconnected(2)));
+    });
+    assert!(!prod.is_consumer_connected());
+    assert!(prod.enqueue_overwrite(3) == Overwrite::Rejected(3));
+}
+
+#[test]
+fn changed() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    block_on(async {
+        // Two values published while no one was looking only count as one change.
+        prod.enqueue_overwrite(1);
+        prod.enqueue_overwrite(2);
+        assert!(cons.changed().await == Ok(()));
+        assert!(cons.peek() == Some(2));
+    });
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            thread::sleep(Duration::from_millis(1));
+            prod.enqueue_overwrite(3);
+        });
+
+        block_on(async {
+            assert!(cons.changed().await == Ok(()));
+            assert!(cons.peek() == Some(3));
+            assert!(cons.changed().await == Err(Closed));
+        });
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn async_iterator() {
+    use std::{
+        async_iter::AsyncIterator,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+    let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+    prod.enqueue(1);
+    assert!(Pin::new(&mut cons).poll_next(&mut cx) == Poll::Ready(Some(1)));
+    assert!(Pin::new(&mut cons).poll_next(&mut cx) == Poll::Pending);
+    drop(prod);
+    assert!(Pin::new(&mut cons).poll_next(&mut cx) == Poll::Ready(None));
+}
+
+#[test]
+fn enqueue_rendezvous_waits_for_peek() {
+    use futures::task::noop_waker_ref;
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll},
+    };
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    let mut rendezvous = pin!(prod.enqueue_rendezvous_async(1));
+    assert!(rendezvous.as_mut().poll(&mut cx) == Poll::Pending);
+
+    // Peeking at the value does not take it.
+    let guard = cons.peek_ref().unwrap();
+    assert!(*guard == 1);
+    assert!(rendezvous.as_mut().poll(&mut cx) == Poll::Pending);
+    drop(guard);
+    assert!(rendezvous.as_mut().poll(&mut cx) == Poll::Pending);
+
+    assert!(cons.dequeue() == Some(1));
+    assert!(rendezvous.as_mut().poll(&mut cx) == Poll::Ready(Ok(())));
+}
+
+#[test]
+fn enqueue_rendezvous_async() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            thread::sleep(Duration::from_millis(1));
+            assert!(cons.dequeue() == Some(1));
+            while cons.is_empty() {}
+        });
+
+        block_on(async {
+            assert!(prod.enqueue_rendezvous_async(1).await == Ok(()));
+            assert!(prod.enqueue_rendezvous_async(2).await == Err(Disconnected(2)));
+        });
+    });
+}
+
+#[test]
+fn select2() {
+    use ssq::future::{select2, Either};
+
+    let mut a = SingleSlotQueue::<u32>::new();
+    let mut b = SingleSlotQueue::<&str>::new();
+    let (mut cons_a, prod_a) = a.split();
+    let (mut cons_b, mut prod_b) = b.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            thread::sleep(Duration::from_millis(10));
+            prod_b.enqueue("b");
+        });
+
+        block_on(async {
+            assert!(select2(&mut cons_a, &mut cons_b).await == Ok(Either::Second("b")));
+            drop(prod_a);
+            // Both producers are gone.
+            assert!(select2(&mut cons_a, &mut cons_b).await == Err(Closed));
+        });
+    });
+}
+
+#[test]
+fn select_array_is_fair() {
+    use futures::task::noop_waker_ref;
+    use ssq::future::select_array;
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll},
+    };
+
+    let mut a = SingleSlotQueue::<u32>::new();
+    let mut b = SingleSlotQueue::<u32>::new();
+    let (cons_a, mut prod_a) = a.split();
+    let (cons_b, mut prod_b) = b.split();
+    let mut conss = [cons_a, cons_b];
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    let mut select = pin!(select_array(&mut conss));
+    assert!(select.as_mut().poll(&mut cx) == Poll::Pending);
+
+    // The second consumer is checked first on the next poll.
+    prod_a.enqueue(0);
+    prod_b.enqueue(1);
+    assert!(select.as_mut().poll(&mut cx) == Poll::Ready(Ok((1, 1))));
+}
+
+#[test]
+fn select2_is_fair() {
+    use futures::task::noop_waker_ref;
+    use ssq::future::{select2, Either};
+    use std::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll},
+    };
+
+    let mut a = SingleSlotQueue::<u32>::new();
+    let (mut cons_a, mut prod_a) = a.split();
+    let mut cx = Context::from_waker(noop_waker_ref());
+
+    // The consumers do not need to share a lifetime.
+    let mut b = SingleSlotQueue::<u32>::new();
+    let (mut cons_b, mut prod_b) = b.split();
+
+    let mut select = pin!(select2(&mut cons_a, &mut cons_b));
+    assert!(select.as_mut().poll(&mut cx) == Poll::Pending);
+
+    prod_a.enqueue(0);
+    prod_b.enqueue(1);
+    assert!(select.as_mut().poll(&mut cx) == Poll::Ready(Ok(Either::Second(1))));
+}
@@ -0,0 +1,103 @@
+use rand::Rng;
+use ssq::bbq::GrantBuffer;
+use std::thread;
+
+#[test]
+fn wraparound() {
+    let mut buf = GrantBuffer::<8>::new();
+    let (mut cons, mut prod) = buf.split();
+
+    prod.grant_exact(6).unwrap().commit(6);
+    cons.read().unwrap().release(4);
+
+    // Not enough room left at the end, so the grant wraps around to the front.
+    let mut grant = prod.grant_exact(3).unwrap();
+    grant.copy_from_slice(b"abc");
+    grant.commit(3);
+    assert!(prod.grant_exact(1).is_none());
+
+    assert!(cons.read().unwrap().len() == 2);
+    cons.read().unwrap().release(2);
+    assert!(&cons.read().unwrap()[..] == b"abc");
+}
+
+#[test]
+fn grant_max_remaining() {
+    let mut buf = GrantBuffer::<8>::new();
+    let (mut cons, mut prod) = buf.split();
+
+    assert!(prod.grant_max_remaining(16).unwrap().len() == 8);
+    prod.grant_max_remaining(16).unwrap().commit(8);
+    assert!(prod.grant_max_remaining(16).is_none());
+
+    cons.read().unwrap().release(3);
+    assert!(prod.grant_max_remaining(16).unwrap().len() == 2);
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn ordered_bytes() {
+    const TOTAL: usize = 5000;
+    let mut buf = GrantBuffer::<64>::new();
+    let (mut cons, mut prod) = buf.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut rng = rand::thread_rng();
+            let mut next = 0;
+            while next < TOTAL {
+                let len = rng.gen_range(1..=16).min(TOTAL - next);
+                if let Some(mut grant) = prod.grant_exact(len) {
+                    for b in grant.iter_mut() {
+                        *b = next as u8;
+                        next += 1;
+                    }
+                    grant.commit(len);
+                }
+            }
+        });
+
+        let mut rng = rand::thread_rng();
+        let mut next = 0;
+        while next < TOTAL {
+            if let Some(grant) = cons.read() {
+                let used = rng.gen_range(1..=grant.len());
+                for b in &grant[..used] {
+                    assert_eq!(*b, next as u8);
+                    next += 1;
+                }
+                grant.release(used);
+            }
+        }
+    });
+}
+
+#[test]
+fn copy_bytes() {
+    let mut buf = GrantBuffer::<8>::new();
+    let (mut cons, mut prod) = buf.split();
+
+    assert!(prod.write_bytes(b"abcdef") == 6);
+    let mut out = [0; 4];
+    assert!(cons.read_bytes(&mut out) == 4);
+    assert!(&out == b"abcd");
+
+    // Both copies go across the end of the buffer.
+    assert!(prod.write_bytes(b"ghijklmn") == 5);
+    let mut out = [0; 8];
+    assert!(cons.read_bytes(&mut out) == 7);
+    assert!(&out[..7] == b"efghijk");
+    assert!(cons.read_bytes(&mut out) == 0);
+}
+
+#[test]
+fn fmt_write() {
+    use std::fmt::Write;
+
+    let mut buf = GrantBuffer::<8>::new();
+    let (mut cons, mut prod) = buf.split();
+
+    assert!(write!(prod, "{}-{}", 12, 34).is_ok());
+    assert!(&cons.read().unwrap()[..] == b"12-34");
+    assert!(write!(prod, "{}", 123_456).is_err());
+}
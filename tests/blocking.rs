@@ -0,0 +1,26 @@
+#![cfg(feature = "std")]
+
+use ssq::SingleSlotQueue;
+use std::{thread, time::Duration};
+
+#[test]
+fn iter_blocking() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                while prod.enqueue(i).is_some() {
+                    thread::yield_now();
+                }
+                if i % 10 == 0 {
+                    // Give the consumer time to park.
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+        });
+
+        assert!(cons.iter_blocking().eq(0..100));
+    });
+}
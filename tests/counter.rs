@@ -0,0 +1,39 @@
+use ssq::counter::Counter;
+use std::thread;
+
+#[test]
+fn no_lost_events() {
+    let counter = Counter::new();
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..250 {
+                    counter.increment();
+                }
+            });
+        }
+
+        let mut total = 0;
+        while total < 1000 {
+            total += counter.take();
+        }
+        assert_eq!(total, 1000);
+    });
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn wait() {
+    let counter = Counter::new();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(std::time::Duration::from_millis(1));
+            counter.add(3);
+        });
+        let mut listener = counter.listen().unwrap();
+        assert!(counter.listen().is_none());
+        assert_eq!(futures::executor::block_on(listener.wait()), 3);
+    });
+}
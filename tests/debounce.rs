@@ -0,0 +1,58 @@
+use ssq::{debounce::Debounced, timestamp::Clock, SingleSlotQueue};
+use std::cell::Cell;
+
+struct FakeClock(Cell<u32>);
+
+impl Clock for FakeClock {
+    type Instant = u32;
+    fn now(&self) -> u32 {
+        self.0.get()
+    }
+    fn duration_since(&self, later: u32, earlier: u32) -> u32 {
+        later.wrapping_sub(earlier)
+    }
+}
+
+#[test]
+fn yields_settled_changes_once() {
+    let mut queue = SingleSlotQueue::<u8>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = Debounced::new(cons, FakeClock(Cell::new(0)), 3);
+    let tick = |cons: &Debounced<'_, u8, FakeClock>, t| cons.clock().0.set(t);
+
+    prod.enqueue(1);
+    assert!(cons.dequeue().is_none());
+    tick(&cons, 3);
+    assert!(cons.dequeue() == Some(1));
+
+    // Repeating the same value is not a change.
+    prod.enqueue(1);
+    tick(&cons, 10);
+    assert!(cons.dequeue().is_none());
+
+    // Bouncing back to the settled value does not yield it again.
+    prod.enqueue(2);
+    assert!(cons.dequeue().is_none());
+    prod.enqueue(1);
+    tick(&cons, 20);
+    assert!(cons.dequeue().is_none());
+
+    prod.enqueue(2);
+    assert!(cons.dequeue().is_none());
+    tick(&cons, 23);
+    assert!(cons.dequeue() == Some(2));
+}
+
+#[test]
+fn tick_counter_wraps() {
+    let mut queue = SingleSlotQueue::<u8>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = Debounced::new(cons, FakeClock(Cell::new(u32::MAX - 1)), 3);
+
+    prod.enqueue(1);
+    assert!(cons.dequeue().is_none());
+    cons.clock().0.set(0);
+    assert!(cons.dequeue().is_none());
+    cons.clock().0.set(1);
+    assert!(cons.dequeue() == Some(1));
+}
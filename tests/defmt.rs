@@ -0,0 +1,16 @@
+#![cfg(feature = "defmt")]
+
+use ssq::{Closed, Consumer, Disconnected, LossyEnqueue, Producer, SingleSlotQueue};
+
+fn assert_format<T: defmt::Format>() {}
+
+#[test]
+fn implements_format() {
+    assert_format::<SingleSlotQueue<u32>>();
+    assert_format::<Consumer<'static, u32>>();
+    assert_format::<Producer<'static, u32>>();
+    assert_format::<Closed>();
+    assert_format::<Disconnected<u32>>();
+    assert_format::<LossyEnqueue>();
+    assert_format::<ssq::frame::FrameError>();
+}
@@ -0,0 +1,29 @@
+use ssq::double::DoubleBuffer;
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn no_tearing() {
+    let mut buf = DoubleBuffer::new([0u32; 64]);
+    let (mut cons, mut prod) = buf.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut i = 1;
+            while i <= 1000 {
+                prod.back_buffer().fill(i);
+                if prod.swap() {
+                    i += 1;
+                }
+            }
+        });
+
+        let mut last = 0;
+        while last < 1000 {
+            let front = cons.read();
+            assert!(front.iter().all(|v| *v == front[0]), "torn read");
+            assert!(front[0] >= last, "values went backwards");
+            last = front[0];
+        }
+    });
+}
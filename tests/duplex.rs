@@ -0,0 +1,32 @@
+use ssq::duplex::Duplex;
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn ping_pong() {
+    let mut duplex = Duplex::<u32, u64>::new();
+    let (mut a, mut b) = duplex.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for _ in 0..100 {
+                let ping = loop {
+                    if let Some(v) = b.dequeue() {
+                        break v;
+                    }
+                };
+                assert!(b.enqueue(u64::from(ping) + 1).is_none());
+            }
+        });
+
+        for i in 0..100 {
+            assert!(a.enqueue(i).is_none());
+            let pong = loop {
+                if let Some(v) = a.dequeue() {
+                    break v;
+                }
+            };
+            assert_eq!(pong, u64::from(i) + 1);
+        }
+    });
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "embassy-time")]
+
+use embassy_time::Duration;
+use futures::executor::block_on;
+use ssq::{
+    future::{DequeueTimeoutError, EnqueueTimeoutError},
+    SingleSlotQueue,
+};
+
+#[test]
+fn dequeue_timeout() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    block_on(async {
+        let timeout = Duration::from_millis(5);
+        assert!(cons.dequeue_timeout(timeout).await == Err(DequeueTimeoutError::Timeout));
+        prod.enqueue(1);
+        assert!(cons.dequeue_timeout(timeout).await == Ok(1));
+    });
+}
+
+#[test]
+fn enqueue_timeout() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    block_on(async {
+        let timeout = Duration::from_millis(5);
+        assert!(prod.enqueue_timeout(1, timeout).await == Ok(()));
+        assert!(prod.enqueue_timeout(2, timeout).await == Err(EnqueueTimeoutError::Timeout(2)));
+        assert!(cons.dequeue() == Some(1));
+    });
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "embedded-dma")]
+
+use embedded_dma::{ReadBuffer, WriteBuffer};
+use ssq::{bbq::GrantBuffer, SingleSlotQueue};
+use std::ptr;
+
+/// Stands in for a DMA transfer out of a buffer, as a HAL would set it up.
+fn dma_read<B: ReadBuffer<Word = u8>>(buf: B, dst: &mut [u8]) -> B {
+    let (ptr, len) = unsafe { buf.read_buffer() };
+    assert!(len == dst.len());
+    unsafe { ptr::copy_nonoverlapping(ptr, dst.as_mut_ptr(), len) };
+    buf
+}
+
+/// Stands in for a DMA transfer into a buffer, as a HAL would set it up.
+fn dma_write<B: WriteBuffer<Word = u8>>(mut buf: B, src: &[u8]) -> B {
+    let (ptr, len) = unsafe { buf.write_buffer() };
+    assert!(len == src.len());
+    unsafe { ptr::copy_nonoverlapping(src.as_ptr(), ptr, len) };
+    buf
+}
+
+#[test]
+fn bbq_grants() {
+    let mut bbq = GrantBuffer::<16>::new();
+    let (mut cons, mut prod) = bbq.split();
+
+    dma_write(prod.grant_exact(4).unwrap(), &[1, 2, 3, 4]).commit(4);
+
+    let mut buf = [0; 4];
+    dma_read(cons.read().unwrap(), &mut buf).release(4);
+    assert!(buf == [1, 2, 3, 4]);
+}
+
+#[test]
+fn ssq_grants() {
+    let mut queue = SingleSlotQueue::<[u8; 4]>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    let grant = dma_write(prod.write_grant().unwrap(), &[5, 6, 7, 8]);
+    // Safety: the whole slot was written by the transfer.
+    unsafe { grant.commit() };
+
+    let mut buf = [0; 4];
+    dma_read(cons.read_grant().unwrap(), &mut buf).release();
+    assert!(buf == [5, 6, 7, 8]);
+}
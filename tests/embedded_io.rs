@@ -0,0 +1,37 @@
+#![cfg(feature = "embedded-io")]
+
+use embedded_io::{Read, ReadReady, Write, WriteReady};
+use ssq::bbq::GrantBuffer;
+use std::thread;
+
+#[test]
+fn io() {
+    let mut buf = GrantBuffer::<8>::new();
+    let (mut cons, mut prod) = buf.split();
+
+    let mut out = [0; 8];
+    assert!(!cons.read_ready().unwrap());
+    assert!(prod.write(b"0123456789").unwrap() == 8);
+    assert!(!prod.write_ready().unwrap());
+    assert!(cons.read_ready().unwrap());
+    cons.read_exact(&mut out[..3]).unwrap();
+    assert!(&out[..3] == b"012");
+    prod.write_all(b"ab").unwrap();
+    assert!(Read::read(&mut cons, &mut out).unwrap() == 7);
+    assert!(&out[..7] == b"34567ab");
+}
+
+#[test]
+fn io_blocks() {
+    let mut buf = GrantBuffer::<4>::new();
+    let (mut cons, mut prod) = buf.split();
+    let data: Vec<u8> = (0..=255).collect();
+
+    thread::scope(|scope| {
+        scope.spawn(|| prod.write_all(&data).unwrap());
+
+        let mut out = [0; 256];
+        cons.read_exact(&mut out).unwrap();
+        assert!(out[..] == data[..]);
+    });
+}
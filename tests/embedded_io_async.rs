@@ -0,0 +1,21 @@
+#![cfg(feature = "embedded-io-async")]
+
+use embedded_io_async::{Read, Write};
+use futures::executor::block_on;
+use ssq::bbq::GrantBuffer;
+use std::thread;
+
+#[test]
+fn io_async() {
+    let mut buf = GrantBuffer::<4>::new();
+    let (mut cons, mut prod) = buf.split();
+    let data: Vec<u8> = (0..=255).collect();
+
+    thread::scope(|scope| {
+        scope.spawn(|| block_on(prod.write_all(&data)).unwrap());
+
+        let mut out = [0; 256];
+        block_on(cons.read_exact(&mut out)).unwrap();
+        assert!(out[..] == data[..]);
+    });
+}
@@ -0,0 +1,48 @@
+use ssq::{exchange::Exchanger, Disconnected};
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn round_trips() {
+    let mut exchanger = Exchanger::<Box<u32>>::new();
+    let (mut a, mut b) = exchanger.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                assert_eq!(*b.exchange(Box::new(i + 1000)).unwrap(), i);
+            }
+        });
+
+        for i in 0..100 {
+            assert_eq!(*a.exchange(Box::new(i)).unwrap(), i + 1000);
+        }
+    });
+}
+
+#[test]
+fn disconnected() {
+    let mut exchanger = Exchanger::<u32>::new();
+    let (mut a, b) = exchanger.split();
+    drop(b);
+    assert!(a.exchange(1) == Err(Disconnected(1)));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn exchange_async() {
+    let mut exchanger = Exchanger::<u32>::new();
+    let (mut a, mut b) = exchanger.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            thread::sleep(std::time::Duration::from_millis(1));
+            assert!(b.exchange(2) == Ok(1));
+        });
+
+        futures::executor::block_on(async {
+            assert!(a.exchange_async(1).await == Ok(2));
+            assert!(a.exchange_async(3).await == Err(Disconnected(3)));
+        });
+    });
+}
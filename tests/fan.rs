@@ -0,0 +1,77 @@
+use ssq::{
+    fan::{zip, FanIn, FanOut},
+    SingleSlotQueue,
+};
+
+#[test]
+fn fan_out() {
+    let mut a = SingleSlotQueue::<String>::new();
+    let mut b = SingleSlotQueue::<String>::new();
+    let (mut cons_a, prod_a) = a.split();
+    let (cons_b, prod_b) = b.split();
+    let mut fan_out = FanOut::new([prod_a, prod_b]);
+
+    assert!(fan_out.enqueue(String::from("one")) == 2);
+    assert!(cons_a.dequeue().as_deref() == Some("one"));
+    // The second queue is still full.
+    assert!(fan_out.enqueue(String::from("two")) == 1);
+    assert!(fan_out.enqueue_overwrite(String::from("three")) == 2);
+
+    drop(cons_b);
+    assert!(fan_out.enqueue_overwrite(String::from("four")) == 1);
+    assert!(cons_a.dequeue().as_deref() == Some("four"));
+
+    let [prod_a, _] = fan_out.into_inner();
+    assert!(prod_a.is_empty());
+}
+
+#[test]
+fn fan_out_empty() {
+    let mut fan_out = FanOut::<u32, 0>::new([]);
+    assert!(fan_out.enqueue(1) == 0);
+}
+
+#[test]
+fn fan_in() {
+    let mut a = SingleSlotQueue::<u32>::new();
+    let mut b = SingleSlotQueue::<u32>::new();
+    let (cons_a, mut prod_a) = a.split();
+    let (cons_b, mut prod_b) = b.split();
+    let mut fan_in = FanIn::new([cons_a, cons_b]);
+
+    assert!(fan_in.dequeue().is_none());
+    assert!(fan_in.is_empty());
+
+    // A busy queue does not starve the other one.
+    prod_a.enqueue(1);
+    prod_b.enqueue(2);
+    assert!(fan_in.dequeue() == Some((0, 1)));
+    prod_a.enqueue(3);
+    assert!(fan_in.dequeue() == Some((1, 2)));
+    assert!(fan_in.dequeue() == Some((0, 3)));
+
+    drop(prod_a);
+    assert!(!fan_in.is_closed());
+    drop(prod_b);
+    assert!(fan_in.is_closed());
+}
+
+#[test]
+fn zip_pairs() {
+    let mut a = SingleSlotQueue::<u32>::new();
+    let mut b = SingleSlotQueue::<i16>::new();
+    let (cons_a, mut prod_a) = a.split();
+    let (cons_b, mut prod_b) = b.split();
+    let mut zipped = zip(cons_a, cons_b);
+
+    prod_a.enqueue(1);
+    assert!(zipped.dequeue().is_none());
+    // The first value was left in its queue, so it can still be overwritten.
+    prod_a.enqueue_overwrite(2);
+    prod_b.enqueue(-2);
+    assert!(zipped.dequeue() == Some((2, -2)));
+    assert!(zipped.dequeue().is_none());
+
+    let (mut cons_a, mut cons_b) = zipped.into_inner();
+    assert!(cons_a.dequeue().is_none() && cons_b.dequeue().is_none());
+}
@@ -0,0 +1,38 @@
+use ssq::flags::EventFlags;
+use std::thread;
+
+#[test]
+fn no_lost_flags() {
+    let flags = EventFlags::new();
+
+    thread::scope(|scope| {
+        for bit in 0..8 {
+            let flags = &flags;
+            scope.spawn(move || flags.set(1 << bit));
+        }
+
+        let mut seen = 0;
+        while seen != 0xff {
+            seen |= flags.take();
+        }
+    });
+    assert_eq!(flags.get(), 0);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn wait_any() {
+    let flags = EventFlags::new();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            flags.set(0b100);
+            thread::sleep(std::time::Duration::from_millis(1));
+            flags.set(0b010);
+        });
+        let mut listener = flags.listen().unwrap();
+        assert!(flags.listen().is_none());
+        assert_eq!(futures::executor::block_on(listener.wait_any(0b011)), 0b010);
+    });
+    assert_eq!(flags.take(), 0b100);
+}
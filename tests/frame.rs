@@ -0,0 +1,69 @@
+use ssq::frame::{FrameError, FrameSlot};
+use std::thread;
+
+#[test]
+fn too_long() {
+    let mut slot = FrameSlot::<4>::new();
+    let (mut cons, mut prod) = slot.split();
+
+    assert!(prod.write_frame(b"hello") == Err(FrameError::TooLong));
+    assert!(prod.write_frame(b"hey") == Ok(()));
+
+    // The frame stays in the slot until it is read into a large enough buffer.
+    assert!(cons.read_frame(&mut [0; 2]) == Err(FrameError::TooLong));
+    assert!(cons.frame_len() == Some(3));
+    assert!(cons.read_frame(&mut [0; 3]) == Ok(3));
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn frames_in_order() {
+    let mut slot = FrameSlot::<32>::new();
+    let (mut cons, mut prod) = slot.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100u8 {
+                let frame = [i; 32];
+                let len = usize::from(i) % 32;
+                while prod.write_frame(&frame[..len]).is_err() {}
+            }
+        });
+
+        let mut buf = [0; 32];
+        for i in 0..100u8 {
+            let len = loop {
+                if let Ok(len) = cons.read_frame(&mut buf) {
+                    break len;
+                }
+            };
+            assert_eq!(len, usize::from(i) % 32);
+            assert!(buf[..len].iter().all(|b| *b == i));
+        }
+    });
+}
+
+#[test]
+fn in_place() {
+    let mut slot = FrameSlot::<8>::new();
+    let (mut cons, mut prod) = slot.split();
+
+    // A toy length-prefixed encoding, standing in for a serializer.
+    fn encode(msg: &[u8]) -> impl FnOnce(&mut [u8]) -> Result<usize, &'static str> + '_ {
+        move |buf| {
+            let frame = buf.get_mut(..msg.len() + 1).ok_or("too long")?;
+            frame[0] = msg.len() as u8;
+            frame[1..].copy_from_slice(msg);
+            Ok(msg.len() + 1)
+        }
+    }
+
+    assert!(prod.write_frame_with(encode(b"too long")) == Ok(Err("too long")));
+    assert!(cons.read_frame_with(|_| ()) == Err(FrameError::Empty));
+    assert!(prod.write_frame_with(encode(b"hi")) == Ok(Ok(())));
+    assert!(prod.write_frame_with(encode(b"no")) == Err(FrameError::Full));
+    assert!(prod.write_frame_with(|_| Ok::<_, ()>(9)) == Err(FrameError::Full));
+    assert!(cons.read_frame_with(|frame| frame == b"\x02hi") == Ok(true));
+    assert!(prod.write_frame_with(|_| Ok::<_, ()>(9)) == Err(FrameError::TooLong));
+    assert!(cons.is_empty());
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "hooks")]
+
+use ssq::{hook::Event, Overwrite, SingleSlotQueue};
+use std::sync::Mutex;
+
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+
+fn record(event: Event) {
+    EVENTS.lock().unwrap().push(event);
+}
+
+#[test]
+fn reports_events() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    queue.set_hook(Some(record));
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.enqueue(1).is_none());
+    assert!(prod.enqueue(2) == Some(2));
+    assert!(prod.enqueue_overwrite(3) == Overwrite::Overwrote(1));
+    assert!(cons.dequeue() == Some(3));
+    assert!(cons.dequeue().is_none());
+
+    assert!(
+        *EVENTS.lock().unwrap()
+            == [
+                Event::Enqueued,
+                Event::Rejected,
+                Event::Enqueued,
+                Event::Overwrote,
+                Event::Dequeued,
+            ]
+    );
+}
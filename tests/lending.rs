@@ -0,0 +1,30 @@
+#![cfg(feature = "lending")]
+
+use ssq::{lending::LendingIterator, Overwrite, SingleSlotQueue};
+
+#[test]
+fn peeks() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(vec![1]);
+    {
+        let mut peeks = cons.peeks();
+        assert!(peeks.next().as_deref() == Some(&vec![1]));
+        assert!(peeks.next().is_none());
+
+        // Overwritten values are skipped.
+        prod.enqueue_overwrite(vec![2]);
+        prod.enqueue_overwrite(vec![3]);
+        {
+            let guard = peeks.next().unwrap();
+            assert!(*guard == [3]);
+            // The producer cannot overwrite a borrowed value.
+            assert!(prod.enqueue_overwrite(vec![4]) == Overwrite::Rejected(vec![4]));
+        }
+        assert!(peeks.next().is_none());
+    }
+
+    // Nothing was taken out of the queue.
+    assert!(cons.dequeue() == Some(vec![3]));
+}
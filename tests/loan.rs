@@ -0,0 +1,53 @@
+use ssq::loan::LoanedQueue;
+use std::thread;
+
+#[test]
+fn borrow_waits_for_reclaim() {
+    let mut queue = LoanedQueue::<Box<u32>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.lend(Box::new(1)).is_none());
+    drop(cons.borrow().unwrap());
+    assert!(prod.lend(Box::new(2)).is_none());
+
+    // The first value has not been reclaimed yet.
+    assert!(cons.borrow().is_none());
+    assert!(prod.reclaim() == Some(Box::new(1)));
+    assert!(**cons.borrow().unwrap() == 2);
+    assert!(prod.reclaim() == Some(Box::new(2)));
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn round_trips() {
+    let mut queue = LoanedQueue::<Box<[u32; 8]>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut buf = Box::new([0; 8]);
+            for i in 1..=100 {
+                buf.fill(i);
+                while let Some(b) = prod.lend(buf) {
+                    buf = b;
+                }
+                buf = loop {
+                    if let Some(b) = prod.reclaim() {
+                        break b;
+                    }
+                };
+                assert!(buf.iter().all(|v| *v == i + 1));
+            }
+        });
+
+        for i in 1..=100 {
+            let mut loan = loop {
+                if let Some(loan) = cons.borrow() {
+                    break loan;
+                }
+            };
+            assert!(loan.iter().all(|v| *v == i));
+            loan.iter_mut().for_each(|v| *v += 1);
+        }
+    });
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "log")]
+
+use log::{Log, Metadata, Record};
+use ssq::{Overwrite, SingleSlotQueue};
+use std::sync::Mutex;
+
+static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Recorder;
+
+impl Log for Recorder {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        if record.target() == "ssq" {
+            MESSAGES.lock().unwrap().push(record.args().to_string());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn logs_events() {
+    log::set_logger(&Recorder).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+
+    assert!(prod.enqueue(1).is_none());
+    assert!(prod.enqueue(2) == Some(2));
+    assert!(prod.enqueue_overwrite(3) == Overwrite::Overwrote(1));
+    drop(cons);
+    drop(prod);
+
+    assert!(
+        *MESSAGES.lock().unwrap()
+            == [
+                "rejected an enqueued value",
+                "overwrote an unread value",
+                "consumer dropped",
+                "producer dropped, queue closed",
+            ]
+    );
+}
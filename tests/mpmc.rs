@@ -0,0 +1,79 @@
+use ssq::{mpmc::AtomicSlot, Overwrite};
+use std::{
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Soundness test that should be run through Miri
+#[test]
+fn many_threads() {
+    let slot = AtomicSlot::<u32>::new();
+    let taken = AtomicU32::new(0);
+
+    thread::scope(|scope| {
+        for t in 0..2 {
+            let slot = &slot;
+            scope.spawn(move || {
+                for i in 0..50 {
+                    while slot.put(t * 50 + i).is_some() {}
+                }
+            });
+        }
+
+        for _ in 0..2 {
+            let (slot, taken) = (&slot, &taken);
+            scope.spawn(move || {
+                while taken.load(Ordering::Relaxed) < 100 {
+                    if slot.take().is_some() {
+                        taken.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    assert!(slot.is_empty());
+}
+
+/// Values are rejected while another thread is accessing the slot. Every value is accounted for
+/// exactly once: handed back when rejected or overwritten, or taken.
+#[test]
+fn put_overwrite_rejected() {
+    let slot = AtomicSlot::<[u32; 1024]>::new();
+    let done = AtomicBool::new(false);
+    // The threads only contend for the slot when they run in parallel.
+    let parallel = thread::available_parallelism().is_ok_and(|n| n.get() > 1);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        let taker = scope.spawn(|| {
+            let mut taken = 0;
+            while !done.load(Ordering::Relaxed) {
+                taken += u32::from(slot.take().is_some());
+            }
+            taken + u32::from(slot.take().is_some())
+        });
+
+        let (mut sent, mut rejected, mut overwritten) = (0, 0, 0);
+        while rejected == 0 && start.elapsed() < Duration::from_secs(2) {
+            sent += 1;
+            match slot.put_overwrite([sent; 1024]) {
+                Overwrite::Enqueued => {}
+                Overwrite::Overwrote(old) => {
+                    assert!(old[0] < sent);
+                    overwritten += 1;
+                }
+                Overwrite::Rejected(val) => {
+                    assert!(val[0] == sent);
+                    rejected += 1;
+                }
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+
+        let taken = taker.join().unwrap();
+        assert!(rejected + overwritten + taken == sent);
+        assert!(rejected > 0 || !parallel);
+    });
+}
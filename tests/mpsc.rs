@@ -0,0 +1,88 @@
+use ssq::{mpsc::MpscSlot, Overwrite};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Soundness test that should be run through Miri
+#[test]
+fn many_producers() {
+    let mut slot = MpscSlot::<u32>::new();
+    let (mut cons, prod) = slot.split();
+
+    thread::scope(|scope| {
+        for t in 0..4 {
+            let mut prod = prod.clone();
+            scope.spawn(move || {
+                for i in 0..100 {
+                    let val = t * 100 + i;
+                    if t % 2 == 0 {
+                        let _ = prod.enqueue(val);
+                    } else {
+                        let _ = prod.enqueue_overwrite(val);
+                    }
+                }
+            });
+        }
+
+        scope.spawn(move || {
+            for _ in 0..500 {
+                let _ = cons.dequeue();
+            }
+        });
+    });
+}
+
+#[test]
+fn enqueue_overwrite() {
+    let mut slot = MpscSlot::<u32>::new();
+    let (mut cons, mut prod) = slot.split();
+
+    assert!(prod.enqueue_overwrite(1) == Overwrite::Enqueued);
+    assert!(prod.enqueue_overwrite(2) == Overwrite::Overwrote(1));
+    assert!(cons.dequeue() == Some(2));
+}
+
+/// Values are rejected while another handle is accessing the slot. Every value is accounted for
+/// exactly once: handed back when rejected or overwritten, or dequeued.
+#[test]
+fn enqueue_overwrite_rejected() {
+    let mut slot = MpscSlot::<[u32; 1024]>::new();
+    let (mut cons, mut prod) = slot.split();
+    let done = AtomicBool::new(false);
+    // The handles only contend for the slot when they run in parallel.
+    let parallel = thread::available_parallelism().is_ok_and(|n| n.get() > 1);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        let consumer = scope.spawn(|| {
+            let mut dequeued = 0;
+            while !done.load(Ordering::Relaxed) {
+                dequeued += u32::from(cons.dequeue().is_some());
+            }
+            dequeued + u32::from(cons.dequeue().is_some())
+        });
+
+        let (mut sent, mut rejected, mut overwritten) = (0, 0, 0);
+        while rejected == 0 && start.elapsed() < Duration::from_secs(2) {
+            sent += 1;
+            match prod.enqueue_overwrite([sent; 1024]) {
+                Overwrite::Enqueued => {}
+                Overwrite::Overwrote(old) => {
+                    assert!(old[0] < sent);
+                    overwritten += 1;
+                }
+                Overwrite::Rejected(val) => {
+                    assert!(val[0] == sent);
+                    rejected += 1;
+                }
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+
+        let dequeued = consumer.join().unwrap();
+        assert!(rejected + overwritten + dequeued == sent);
+        assert!(rejected > 0 || !parallel);
+    });
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "nb")]
+
+use ssq::{Closed, Disconnected, SingleSlotQueue};
+use std::thread;
+
+#[test]
+fn would_block() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.dequeue_nb() == Err(nb::Error::WouldBlock));
+    assert!(prod.enqueue_nb(1) == Ok(()));
+    assert!(prod.enqueue_nb(2) == Err(nb::Error::WouldBlock));
+    assert!(cons.dequeue_nb() == Ok(1));
+
+    drop(prod);
+    assert!(cons.dequeue_nb() == Err(nb::Error::Other(Closed)));
+    drop(cons);
+
+    let (cons, mut prod) = queue.split();
+    drop(cons);
+    assert!(prod.enqueue_nb(3) == Err(nb::Error::Other(Disconnected(3))));
+}
+
+#[test]
+fn block() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                nb::block!(prod.enqueue_nb(i)).unwrap();
+            }
+        });
+
+        for i in 0..100 {
+            assert!(nb::block!(cons.dequeue_nb()) == Ok(i));
+        }
+        assert!(nb::block!(cons.dequeue_nb()) == Err(Closed));
+    });
+}
@@ -0,0 +1,32 @@
+use ssq::notify::Notifier;
+use std::thread;
+
+#[test]
+fn notify_across_threads() {
+    let event = Notifier::new();
+
+    thread::scope(|scope| {
+        scope.spawn(|| event.notify());
+        while !event.take() {}
+    });
+    assert!(!event.is_notified());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn wait() {
+    let event = Notifier::new();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            thread::sleep(std::time::Duration::from_millis(1));
+            event.notify();
+        });
+        let mut listener = event.listen().unwrap();
+        // There can only be one listener at a time.
+        assert!(event.listen().is_none());
+        futures::executor::block_on(listener.wait());
+    });
+    assert!(event.listen().is_some());
+    assert!(!event.take());
+}
@@ -0,0 +1,57 @@
+use ssq::oneshot::Channel;
+use std::thread;
+
+#[test]
+fn across_threads() {
+    let mut chan = Channel::<u32>::new();
+    let (mut cons, prod) = chan.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            assert!(prod.enqueue(7).is_none());
+        });
+
+        let val = loop {
+            if let Some(v) = cons.dequeue() {
+                break v;
+            }
+        };
+        assert_eq!(val, 7);
+    });
+}
+
+#[test]
+fn closed_without_value() {
+    let mut chan = Channel::<u32>::new();
+    let (mut cons, prod) = chan.split();
+    drop(prod);
+    assert!(cons.is_closed());
+    assert!(cons.dequeue().is_none());
+}
+
+#[test]
+fn consumer_dropped() {
+    let mut chan = Channel::<u32>::new();
+    let (cons, prod) = chan.split();
+    drop(cons);
+    assert!(!prod.is_consumer_connected());
+    assert_eq!(prod.enqueue(3), Some(3));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn await_value() {
+    let mut chan = Channel::<u32>::new();
+    let (cons, prod) = chan.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            prod.enqueue(5);
+        });
+        assert_eq!(futures::executor::block_on(cons), Ok(5));
+    });
+
+    let (cons, prod) = chan.split();
+    drop(prod);
+    assert_eq!(futures::executor::block_on(cons), Err(ssq::Closed));
+}
@@ -0,0 +1,43 @@
+use ssq::pingpong::PingPong;
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn halves_in_order() {
+    let mut buf = PingPong::<u32, 32>::new(0);
+    let (mut cons, mut prod) = buf.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut i = 1;
+            while i <= 100 {
+                if let Some(half) = prod.buffer() {
+                    half.fill(i);
+                    assert!(prod.commit());
+                    i += 1;
+                }
+            }
+        });
+
+        let mut expected = 1;
+        while expected <= 100 {
+            if let Some(half) = cons.read() {
+                assert!(half.iter().all(|v| *v == expected));
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[test]
+fn overrun() {
+    let mut buf = PingPong::<u8, 2>::new(0);
+    let (mut cons, mut prod) = buf.split();
+
+    assert!(prod.commit());
+    assert!(prod.commit());
+    assert!(!prod.commit());
+
+    drop(cons.read());
+    assert!(prod.commit());
+}
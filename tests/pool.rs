@@ -0,0 +1,57 @@
+use ssq::{
+    pool::{Lease, SlotPool},
+    SingleSlotQueue,
+};
+use std::{rc::Rc, thread};
+
+#[test]
+fn exhaustion() {
+    let pool = SlotPool::<Rc<u32>, 2>::new();
+    let val = Rc::new(0);
+
+    let a = pool.alloc(val.clone()).ok().unwrap();
+    let b = pool.alloc(val.clone()).ok().unwrap();
+    assert!(pool.alloc(val.clone()).is_err());
+    assert!(pool.available() == 0);
+
+    drop(a);
+    assert!(Rc::strong_count(&val) == 2);
+    assert!(*b.into_inner() == 0);
+    assert!(pool.available() == 2);
+    assert!(Rc::strong_count(&val) == 1);
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn leases_through_queue() {
+    let pool = SlotPool::<[u32; 16], 3>::new();
+    let mut queue = SingleSlotQueue::<Lease<'_, [u32; 16]>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        let pool = &pool;
+        scope.spawn(move || {
+            for i in 0..200 {
+                let mut lease = loop {
+                    if let Ok(lease) = pool.alloc([0; 16]) {
+                        break lease;
+                    }
+                };
+                lease.fill(i);
+                while let Some(l) = prod.enqueue(lease) {
+                    lease = l;
+                }
+            }
+        });
+
+        for i in 0..200 {
+            let lease = loop {
+                if let Some(lease) = cons.dequeue() {
+                    break lease;
+                }
+            };
+            assert!(lease.iter().all(|v| *v == i));
+        }
+    });
+    assert!(pool.available() == 3);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "postcard")]
+
+use serde::{Deserialize, Serialize};
+use ssq::frame::{FrameError, FrameSlot, MessageError};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+enum Message {
+    Ping,
+    Reading { channel: u8, value: i32 },
+    Log(String),
+}
+
+#[test]
+fn round_trip() {
+    let mut slot = FrameSlot::<16>::new();
+    let (mut cons, mut prod) = slot.split();
+
+    assert!(cons.recv::<Message>() == Err(MessageError::Frame(FrameError::Empty)));
+
+    for msg in [
+        Message::Ping,
+        Message::Reading {
+            channel: 3,
+            value: -1000,
+        },
+        Message::Log("hello".into()),
+    ] {
+        assert!(prod.send(&msg) == Ok(()));
+        assert!(prod.send(&msg) == Err(MessageError::Frame(FrameError::Full)));
+        assert!(cons.recv::<Message>() == Ok(msg));
+    }
+
+    // Messages of different types go through the same slot.
+    assert!(prod.send(&(1u16, true)) == Ok(()));
+    assert!(cons.recv::<(u16, bool)>() == Ok((1, true)));
+}
+
+#[test]
+fn errors() {
+    let mut slot = FrameSlot::<4>::new();
+    let (mut cons, mut prod) = slot.split();
+
+    let long = Message::Log("too long".into());
+    assert!(prod.send(&long) == Err(MessageError::Frame(FrameError::TooLong)));
+    assert!(cons.is_empty());
+
+    // A frame that does not deserialize is dropped.
+    assert!(prod.write_frame(&[9]) == Ok(()));
+    assert!(matches!(
+        cons.recv::<Message>(),
+        Err(MessageError::Postcard(_))
+    ));
+    assert!(cons.is_empty());
+}
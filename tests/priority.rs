@@ -0,0 +1,33 @@
+use ssq::priority::PriorityMailbox;
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn urgent_not_starved() {
+    let mut mailbox = PriorityMailbox::<(bool, u32)>::new();
+    let (mut cons, mut prod) = mailbox.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                while prod.enqueue((false, i)).is_some() {}
+                while prod.enqueue_urgent((true, i)).is_some() {}
+            }
+        });
+
+        let (mut routine, mut urgent) = (0, 0);
+        while routine < 100 || urgent < 100 {
+            match cons.dequeue() {
+                Some((true, v)) => {
+                    assert_eq!(v, urgent);
+                    urgent += 1;
+                }
+                Some((false, v)) => {
+                    assert_eq!(v, routine);
+                    routine += 1;
+                }
+                None => {}
+            }
+        }
+    });
+}
@@ -0,0 +1,53 @@
+use ssq::{ratelimit::RateLimited, timestamp::Clock, SingleSlotQueue};
+use std::cell::Cell;
+
+struct FakeClock(Cell<u32>);
+
+impl Clock for FakeClock {
+    type Instant = u32;
+    fn now(&self) -> u32 {
+        self.0.get()
+    }
+    fn duration_since(&self, later: u32, earlier: u32) -> u32 {
+        later.wrapping_sub(earlier)
+    }
+}
+
+#[test]
+fn one_per_period() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, prod) = queue.split();
+    let mut prod = RateLimited::new(prod, FakeClock(Cell::new(0)), 5);
+
+    let mut received = Vec::new();
+    for t in 0..20 {
+        prod.clock().0.set(t);
+        prod.enqueue_latest(t);
+        if let Some(v) = cons.dequeue() {
+            received.push(v);
+        }
+    }
+    assert_eq!(received, [0, 5, 10, 15]);
+
+    // A full queue does not count as letting a value through.
+    prod.clock().0.set(20);
+    assert!(prod.enqueue(20).is_none());
+    prod.clock().0.set(25);
+    assert!(prod.enqueue(25) == Some(25));
+    assert!(cons.dequeue() == Some(20));
+    assert!(prod.enqueue(26).is_none());
+}
+
+#[test]
+fn tick_counter_wraps() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, prod) = queue.split();
+    let mut prod = RateLimited::new(prod, FakeClock(Cell::new(u32::MAX - 1)), 5);
+
+    assert!(prod.enqueue(1).is_none());
+    assert!(cons.dequeue() == Some(1));
+    prod.clock().0.set(2);
+    assert!(prod.enqueue(2) == Some(2));
+    prod.clock().0.set(3);
+    assert!(prod.enqueue(3).is_none());
+}
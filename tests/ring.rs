@@ -0,0 +1,114 @@
+use ssq::ring::SlotQueue;
+use std::{cell::Cell, rc::Rc, thread};
+
+#[test]
+fn fifo_wraparound() {
+    let mut queue = SlotQueue::<u32, 3>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    for round in 0..10 {
+        for i in 0..3 {
+            assert!(prod.enqueue(round * 3 + i).is_none());
+        }
+        assert!(prod.enqueue(99) == Some(99));
+        assert!(cons.len() == 3);
+        assert!(cons.peek() == Some(round * 3));
+        for i in 0..3 {
+            assert!(cons.dequeue() == Some(round * 3 + i));
+        }
+        assert!(cons.is_empty());
+    }
+}
+
+#[test]
+fn drops_remaining_values() {
+    struct CountDrop(Rc<Cell<usize>>);
+    impl Drop for CountDrop {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    {
+        let mut queue = SlotQueue::<CountDrop, 4>::new();
+        let (mut cons, mut prod) = queue.split();
+        for _ in 0..3 {
+            assert!(prod.enqueue(CountDrop(drops.clone())).is_none());
+        }
+        drop(cons.dequeue());
+        assert!(drops.get() == 1);
+    }
+    assert!(drops.get() == 3);
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn ordered() {
+    let mut queue = SlotQueue::<u32, 4>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..500 {
+                while prod.enqueue(i).is_some() {}
+            }
+        });
+
+        scope.spawn(move || {
+            for i in 0..500 {
+                loop {
+                    if let Some(v) = cons.dequeue() {
+                        assert!(v == i);
+                        break;
+                    }
+                }
+            }
+        });
+    });
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn heap_ordered() {
+    use ssq::ring::HeapSlotQueue;
+
+    let mut queue = HeapSlotQueue::<u32>::new(5);
+    assert!(queue.capacity() == 5);
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..500 {
+                while prod.enqueue(i).is_some() {}
+            }
+        });
+
+        scope.spawn(move || {
+            for i in 0..500 {
+                loop {
+                    if let Some(v) = cons.dequeue() {
+                        assert!(v == i);
+                        break;
+                    }
+                }
+            }
+        });
+    });
+}
+
+#[test]
+fn slice_storage() {
+    use ssq::ring::SliceSlotQueue;
+    use std::mem::MaybeUninit;
+
+    let mut storage = [const { MaybeUninit::<u32>::uninit() }; 2];
+    let mut queue = SliceSlotQueue::from_storage(&mut storage);
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.enqueue(1).is_none());
+    assert!(prod.enqueue(2).is_none());
+    assert!(prod.enqueue(3) == Some(3));
+    assert!(cons.dequeue() == Some(1));
+    assert!(cons.dequeue() == Some(2));
+}
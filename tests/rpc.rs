@@ -0,0 +1,81 @@
+use ssq::rpc::RpcChannel;
+use std::thread;
+
+#[test]
+fn handle_requests() {
+    let mut chan = RpcChannel::<u32, u32>::new();
+    let (mut caller, mut handler) = chan.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut handled = 0;
+            while handled < 100 {
+                if handler.handle(|req| req * 2) {
+                    handled += 1;
+                }
+            }
+        });
+
+        for i in 0..100 {
+            while caller.request(i).is_some() {}
+            let resp = loop {
+                if let Some(resp) = caller.response() {
+                    break resp;
+                }
+            };
+            assert_eq!(resp, i * 2);
+        }
+    });
+}
+
+#[test]
+fn handle_keeps_request_without_room() {
+    let mut chan = RpcChannel::<u32, u32>::new();
+    let (mut caller, mut handler) = chan.split();
+
+    assert!(caller.request(1).is_none());
+    assert!(handler.handle(|req| req));
+    assert!(caller.request(2).is_none());
+
+    // The previous response has not been read, so the request is left pending.
+    assert!(!handler.handle(|_| unreachable!()));
+    assert!(caller.response() == Some(1));
+    assert!(handler.handle(|req| req));
+    assert!(caller.response() == Some(2));
+
+    // Nobody is left to read the response.
+    assert!(caller.request(3).is_none());
+    drop(caller);
+    assert!(!handler.handle(|_| unreachable!()));
+    assert!(handler.request() == Some(3));
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn call() {
+    use ssq::rpc::CallError;
+
+    let mut chan = RpcChannel::<u32, u32>::new();
+    let (mut caller, mut handler) = chan.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            futures::executor::block_on(async {
+                while let Ok(req) = handler.request_async().await {
+                    if req == 3 {
+                        // Drop the handler without responding.
+                        break;
+                    }
+                    handler.respond(req + 1);
+                }
+            });
+        });
+
+        futures::executor::block_on(async {
+            assert_eq!(caller.call(1).await, Ok(2));
+            assert_eq!(caller.call(2).await, Ok(3));
+            assert_eq!(caller.call(3).await, Err(CallError::Closed));
+            assert_eq!(caller.call(4).await, Err(CallError::Disconnected(4)));
+        });
+    });
+}
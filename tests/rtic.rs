@@ -0,0 +1,103 @@
+#![cfg(feature = "rtic-time")]
+
+use futures::{
+    executor::block_on,
+    future::{self, Either},
+};
+use rtic_time::{Monotonic, TimeoutError};
+use ssq::{
+    future::{DequeueTimeoutError, EnqueueTimeoutError},
+    SingleSlotQueue,
+};
+use std::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Host-side monotonic, waking delayed tasks from a helper thread.
+struct StdMono;
+
+impl Monotonic for StdMono {
+    type Instant = Instant;
+    type Duration = Duration;
+
+    fn now() -> Instant {
+        Instant::now()
+    }
+
+    async fn delay(duration: Duration) {
+        Self::delay_until(Self::now() + duration).await
+    }
+
+    async fn delay_until(instant: Instant) {
+        let mut spawned = false;
+        poll_fn(|cx| {
+            if Self::now() >= instant {
+                return Poll::Ready(());
+            }
+            if !spawned {
+                spawned = true;
+                let waker = cx.waker().clone();
+                thread::spawn(move || {
+                    thread::sleep(instant - Instant::now());
+                    waker.wake();
+                });
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    async fn timeout_at<F: Future>(instant: Instant, future: F) -> Result<F::Output, TimeoutError> {
+        match future::select(pin!(future), pin!(Self::delay_until(instant))).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(TimeoutError),
+        }
+    }
+
+    async fn timeout_after<F: Future>(
+        duration: Duration,
+        future: F,
+    ) -> Result<F::Output, TimeoutError> {
+        Self::timeout_at(Self::now() + duration, future).await
+    }
+}
+
+#[test]
+fn dequeue_timeout() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    block_on(async {
+        let timeout = Duration::from_millis(5);
+        assert!(
+            cons.dequeue_timeout_after::<StdMono>(timeout).await
+                == Err(DequeueTimeoutError::Timeout)
+        );
+        prod.enqueue(1);
+        assert!(
+            cons.dequeue_timeout_at::<StdMono>(Instant::now() + timeout)
+                .await
+                == Ok(1)
+        );
+    });
+}
+
+#[test]
+fn enqueue_timeout() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    block_on(async {
+        let timeout = Duration::from_millis(5);
+        assert!(prod.enqueue_timeout_after::<StdMono>(1, timeout).await == Ok(()));
+        assert!(
+            prod.enqueue_timeout_after::<StdMono>(2, timeout).await
+                == Err(EnqueueTimeoutError::Timeout(2))
+        );
+        assert!(cons.dequeue() == Some(1));
+    });
+}
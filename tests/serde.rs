@@ -0,0 +1,51 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use ssq::SingleSlotQueue;
+
+#[test]
+fn serializes_queue() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    assert!(serde_json::to_value(&queue).unwrap() == json!({ "state": "Empty", "value": null }));
+
+    let (cons, mut prod) = queue.split();
+    assert!(prod.enqueue(7).is_none());
+    drop((cons, prod));
+    assert!(serde_json::to_value(&queue).unwrap() == json!({ "state": "Full", "value": 7 }));
+}
+
+#[test]
+fn serializes_from_consumer() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+    assert!(serde_json::to_value(&cons).unwrap() == json!({ "state": "Empty", "value": null }));
+
+    prod.enqueue(7);
+    assert!(serde_json::to_value(&cons).unwrap() == json!({ "state": "Full", "value": 7 }));
+
+    // Serializing does not dequeue the value.
+    assert!(cons.dequeue() == Some(7));
+}
+
+#[test]
+fn serialize_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    struct Boom;
+
+    impl serde::Serialize for Boom {
+        fn serialize<S: serde::Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+            panic!("boom");
+        }
+    }
+
+    let mut queue = SingleSlotQueue::<Boom>::new();
+    let (mut cons, mut prod) = queue.split();
+    assert!(prod.enqueue(Boom).is_none());
+    assert!(catch_unwind(AssertUnwindSafe(|| serde_json::to_value(&cons))).is_err());
+
+    // The slot was given back, with the value left in it.
+    assert!(prod.enqueue(Boom).is_some());
+    assert!(cons.dequeue().is_some());
+    assert!(prod.enqueue(Boom).is_none());
+}
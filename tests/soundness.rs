@@ -1,6 +1,6 @@
 //! Soundness tests that should be run through Miri
 use rand::random;
-use ssq::SingleSlotQueue;
+use ssq::{LossyEnqueue, Overwrite, SingleSlotQueue};
 use std::thread;
 
 #[test]
@@ -77,3 +77,794 @@ fn peek() {
         consume.join().unwrap();
     });
 }
+
+#[test]
+fn enqueue_overwrite_is_ordered() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        let feed = scope.spawn(|| {
+            for i in 1..=500 {
+                // The value is handed back if the consumer is mid-dequeue; retry until it sticks.
+                while prod.enqueue_overwrite(i) == Overwrite::Rejected(i) {}
+            }
+        });
+
+        let consume = scope.spawn(|| {
+            let mut last = 0;
+            while last != 500 {
+                if let Some(v) = cons.dequeue() {
+                    assert!(v > last);
+                    last = v;
+                }
+            }
+        });
+
+        feed.join().unwrap();
+        consume.join().unwrap();
+    });
+}
+
+#[test]
+fn from_storage() {
+    use std::mem::MaybeUninit;
+
+    static mut STORAGE: MaybeUninit<u32> = MaybeUninit::uninit();
+
+    // SAFETY: this is the only place `STORAGE` is used.
+    let mut queue = SingleSlotQueue::from_storage(unsafe { &mut *std::ptr::addr_of_mut!(STORAGE) });
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            for i in 1..=500 {
+                while prod.enqueue(i).is_some() {}
+            }
+        });
+
+        scope.spawn(|| {
+            let mut last = 0;
+            while last != 500 {
+                if let Some(v) = cons.dequeue() {
+                    assert!(v == last + 1);
+                    last = v;
+                }
+            }
+        });
+    });
+}
+
+#[test]
+fn enqueue_rendezvous() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                while cons.dequeue() != Some(i) {}
+            }
+            // Leave the final value in the queue, and go away.
+            while cons.is_empty() {}
+        });
+
+        for i in 0..100 {
+            assert!(prod.enqueue_rendezvous(i).is_ok());
+        }
+        assert!(prod.enqueue_rendezvous(100) == Err(ssq::Disconnected(100)));
+    });
+}
+
+#[test]
+fn enqueue_merge() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for _ in 0..500 {
+                while prod.enqueue_merge(1, |old, new| old + new).is_some() {}
+            }
+        });
+
+        // Every value eventually makes it through, merged or not.
+        let mut total = 0;
+        while total < 500 {
+            total += cons.dequeue().unwrap_or(0);
+        }
+        assert!(total == 500);
+    });
+}
+
+#[test]
+fn enqueue_merge_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(1);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        prod.enqueue_merge(2, |_, _| panic!("merge failed"));
+    }));
+    assert!(r.is_err());
+
+    // The old value was lost, but the queue is usable again.
+    assert!(cons.dequeue().is_none());
+    assert!(prod.enqueue(3).is_none());
+    assert!(cons.dequeue() == Some(3));
+}
+
+#[test]
+fn enqueue_overwrite_recycle() {
+    let mut queue = SingleSlotQueue::<Box<u32>>::new();
+    let mut recycled = SingleSlotQueue::<Box<u32>>::new();
+    let (mut cons, mut prod) = queue.split();
+    let (mut bin, mut recycle) = recycled.split();
+
+    assert!(prod
+        .enqueue_overwrite_recycle(Box::new(1), &mut recycle)
+        .is_none());
+    assert!(prod
+        .enqueue_overwrite_recycle(Box::new(2), &mut recycle)
+        .is_none());
+    assert!(bin.dequeue() == Some(Box::new(1)));
+
+    // The recycle queue is full, so the displaced value is handed back.
+    assert!(prod
+        .enqueue_overwrite_recycle(Box::new(3), &mut recycle)
+        .is_none());
+    assert!(prod.enqueue_overwrite_recycle(Box::new(4), &mut recycle) == Some(Box::new(3)));
+    assert!(cons.dequeue() == Some(Box::new(4)));
+}
+
+#[test]
+fn enqueue_overwrite_returns_displaced() {
+    use std::rc::Rc;
+
+    let val = Rc::new(());
+    {
+        let mut queue = SingleSlotQueue::<Rc<()>>::new();
+        let (_cons, mut prod) = queue.split();
+
+        assert!(prod.enqueue_overwrite(val.clone()) == Overwrite::Enqueued);
+        let displaced = prod.enqueue_overwrite(val.clone()).into_inner().unwrap();
+        assert!(Rc::ptr_eq(&displaced, &val));
+    }
+
+    // Nothing was leaked.
+    assert!(Rc::strong_count(&val) == 1);
+}
+
+#[test]
+fn enqueue_lossy() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+
+    assert!(prod.enqueue_lossy(1) == LossyEnqueue::Enqueued);
+    assert!(prod.enqueue_lossy(2) == LossyEnqueue::Overwrote);
+    drop(cons);
+    assert!(prod.enqueue_lossy(3) == LossyEnqueue::Dropped);
+}
+
+#[test]
+fn debug() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    assert!(format!("{queue:?}") == "SingleSlotQueue { state: Empty }");
+
+    let (cons, mut prod) = queue.split();
+    assert!(prod.enqueue(7).is_none());
+    assert!(format!("{cons:?}") == "Consumer { state: Full, value: 7, closed: false }");
+    drop(cons);
+    assert!(format!("{prod:?}") == "Producer { state: Full, value: 7, consumer_connected: false }");
+    drop(prod);
+    assert!(format!("{queue:?}") == "SingleSlotQueue { state: Full, value: 7 }");
+}
+
+#[test]
+fn errors_display() {
+    use ssq::{Closed, DequeueError, Disconnected, EnqueueError};
+
+    fn assert_error<E: std::error::Error>(_: &E) {}
+
+    assert_error(&Closed);
+    assert_error(&Disconnected(1));
+    assert_error(&EnqueueError::Full(1));
+    assert_error(&DequeueError::Empty);
+    assert!(Closed.to_string() == "queue closed");
+    assert!(Disconnected(1).to_string() == "consumer disconnected");
+    assert!(EnqueueError::Full(1).to_string() == "queue full");
+    assert!(DequeueError::WouldBlock.to_string() == "queue busy");
+}
+
+#[test]
+fn try_enqueue_dequeue() {
+    use ssq::{DequeueError, EnqueueError};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.try_dequeue() == Err(DequeueError::Empty));
+    assert!(prod.try_enqueue(1) == Ok(()));
+    assert!(prod.try_enqueue(2) == Err(EnqueueError::Full(2)));
+    assert!(cons.try_dequeue() == Ok(1));
+
+    assert!(prod.try_enqueue(3) == Ok(()));
+    drop(prod);
+    assert!(cons.try_dequeue() == Ok(3));
+    assert!(cons.try_dequeue() == Err(DequeueError::Closed));
+    drop(cons);
+
+    let (cons, mut prod) = queue.split();
+    drop(cons);
+    assert!(prod.try_enqueue(4) == Err(EnqueueError::Disconnected(4)));
+    assert!(EnqueueError::Disconnected(4).into_inner() == 4);
+}
+
+#[test]
+fn try_dequeue() {
+    use ssq::DequeueError;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    thread::scope(|scope| {
+        let feed = scope.spawn(move || {
+            for i in 0..500 {
+                prod.enqueue_overwrite(i);
+            }
+        });
+
+        let consume = scope.spawn(move || {
+            let mut last = None;
+            loop {
+                match cons.try_dequeue() {
+                    Ok(v) => {
+                        // Values are never seen out of order.
+                        assert!(last.is_none_or(|l| v > l));
+                        last = Some(v);
+                    }
+                    Err(DequeueError::Empty | DequeueError::WouldBlock) => thread::yield_now(),
+                    Err(DequeueError::Closed) => break,
+                }
+            }
+        });
+
+        feed.join().unwrap();
+        consume.join().unwrap();
+    });
+}
+
+#[test]
+fn try_enqueue_overwrite() {
+    use ssq::EnqueueError;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+
+    assert!(prod.try_enqueue_overwrite(1) == Ok(None));
+    assert!(prod.try_enqueue_overwrite(2) == Ok(Some(1)));
+    drop(cons);
+    assert!(prod.try_enqueue_overwrite(3) == Err(EnqueueError::Disconnected(3)));
+}
+
+#[test]
+fn try_peek() {
+    use ssq::DequeueError;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.try_peek() == Err(DequeueError::Empty));
+    prod.enqueue(1);
+    assert!(cons.try_peek() == Ok(1));
+    assert!(cons.try_peek() == Ok(1));
+    drop(prod);
+    assert!(cons.try_peek() == Ok(1));
+    assert!(cons.dequeue() == Some(1));
+    assert!(cons.try_peek() == Err(DequeueError::Closed));
+}
+
+#[test]
+fn peek_with() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.peek_with(|v| v.len()).is_none());
+    prod.enqueue(vec![1, 2, 3]);
+    assert!(cons.peek_with(|v| v.len()) == Some(3));
+    assert!(cons.peek_with(|v| v[1]) == Some(2));
+    assert!(cons.dequeue() == Some(vec![1, 2, 3]));
+}
+
+#[test]
+fn peek_with_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(1);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        cons.peek_with(|_| panic!("inspection failed"));
+    }));
+    assert!(r.is_err());
+
+    // The slot was given back, with the value left in it.
+    assert!(prod.enqueue_overwrite(2) == Overwrite::Overwrote(1));
+}
+
+#[test]
+fn peek_ref() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.peek_ref().is_none());
+    prod.enqueue(vec![1, 2, 3]);
+    {
+        let guard = cons.peek_ref().unwrap();
+        assert!(*guard == [1, 2, 3]);
+        // The producer cannot overwrite the value while it is borrowed.
+        assert!(prod.enqueue_overwrite(vec![4]) == Overwrite::Rejected(vec![4]));
+        assert!(*guard == [1, 2, 3]);
+    }
+    assert!(prod.enqueue_overwrite(vec![4]) == Overwrite::Overwrote(vec![1, 2, 3]));
+    assert!(cons.dequeue() == Some(vec![4]));
+}
+
+#[test]
+fn peek_cloned() {
+    let mut queue = SingleSlotQueue::<String>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.peek_cloned().is_none());
+    prod.enqueue(String::from("hello"));
+    assert!(cons.peek_cloned().as_deref() == Some("hello"));
+    assert!(cons.dequeue().as_deref() == Some("hello"));
+}
+
+#[test]
+fn peek_mut() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.peek_mut(|v| v.push(0)).is_none());
+    prod.enqueue(vec![1]);
+    assert!(
+        cons.peek_mut(|v| {
+            v.push(2);
+            v.len()
+        }) == Some(2)
+    );
+    assert!(cons.dequeue() == Some(vec![1, 2]));
+}
+
+#[test]
+fn peek_mut_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(1);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        cons.peek_mut(|v| {
+            *v = 2;
+            panic!("patch failed");
+        });
+    }));
+    assert!(r.is_err());
+
+    // The slot was given back, with the value as the closure left it.
+    assert!(prod.enqueue_overwrite(3) == Overwrite::Overwrote(2));
+    assert!(cons.dequeue() == Some(3));
+}
+
+#[test]
+fn modify() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.modify(|v| v.push(0), || vec![1]));
+    assert!(prod.modify(|v| v.push(2), || vec![0]));
+    assert!(cons.dequeue() == Some(vec![1, 2]));
+
+    drop(cons);
+    assert!(!prod.modify(|v| v.push(3), || vec![3]));
+}
+
+#[test]
+fn modify_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        prod.modify(|_| (), || panic!("insert failed"));
+    }));
+    assert!(r.is_err());
+    assert!(cons.dequeue().is_none());
+
+    prod.enqueue(1);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        prod.modify(|_| panic!("modify failed"), || 0);
+    }));
+    assert!(r.is_err());
+
+    // The queue was left as it was.
+    assert!(prod.modify(|v| *v += 1, || 0));
+    assert!(cons.dequeue() == Some(2));
+}
+
+#[test]
+fn enqueue_with() {
+    use ssq::EnqueueError;
+
+    let mut queue = SingleSlotQueue::<[u8; 1024]>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.enqueue_with(|| [1; 1024]).is_ok());
+    // The closure is not called when the queue is full.
+    let err = prod.enqueue_with(|| -> [u8; 1024] { unreachable!() });
+    assert!(matches!(err, Err(EnqueueError::Full(_))));
+    assert!(cons.dequeue() == Some([1; 1024]));
+}
+
+#[test]
+fn enqueue_with_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        let _ = prod.enqueue_with(|| panic!("build failed"));
+    }));
+    assert!(r.is_err());
+
+    // The slot was given back, still empty.
+    assert!(cons.dequeue().is_none());
+    assert!(prod.enqueue_with(|| 1).is_ok());
+    assert!(cons.dequeue() == Some(1));
+}
+
+#[test]
+fn enqueue_init() {
+    use ssq::{init::init_from_closure, EnqueueError};
+
+    let mut queue = SingleSlotQueue::<[u32; 256]>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    let fail = unsafe { init_from_closure(|_: *mut [u32; 256]| Err("no data")) };
+    assert!(matches!(prod.enqueue_init(fail), Ok(Err("no data"))));
+    assert!(cons.dequeue().is_none());
+
+    let fill = |n| unsafe {
+        init_from_closure(move |slot: *mut [u32; 256]| {
+            for i in 0..256 {
+                slot.cast::<u32>().add(i).write(n);
+            }
+            Ok::<_, ()>(())
+        })
+    };
+    assert!(matches!(prod.enqueue_init(fill(1)), Ok(Ok(()))));
+    assert!(matches!(
+        prod.enqueue_init(fill(2)),
+        Err(EnqueueError::Full(_))
+    ));
+    assert!(cons.dequeue() == Some([1; 256]));
+
+    let r = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let panics =
+            unsafe { init_from_closure(|_: *mut [u32; 256]| -> Result<(), ()> { panic!() }) };
+        let _ = prod.enqueue_init(panics);
+    }));
+    assert!(r.is_err());
+    assert!(cons.dequeue().is_none());
+    assert!(matches!(prod.enqueue_init(fill(3)), Ok(Ok(()))));
+}
+
+#[test]
+fn dequeue_with() {
+    use std::rc::Rc;
+
+    let val = Rc::new(());
+    {
+        let mut queue = SingleSlotQueue::<(Rc<()>, [u8; 512])>::new();
+        let (mut cons, mut prod) = queue.split();
+
+        assert!(cons.dequeue_with(|_| ()).is_none());
+        prod.enqueue((val.clone(), [3; 512]));
+        assert!(
+            cons.dequeue_with(|(_, buf)| buf.iter().map(|&b| b as u32).sum::<u32>()) == Some(1536)
+        );
+        assert!(cons.is_empty());
+    }
+
+    // The value was dropped in place.
+    assert!(Rc::strong_count(&val) == 1);
+}
+
+#[test]
+fn dequeue_with_panic() {
+    use std::{
+        panic::{catch_unwind, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    let val = Rc::new(());
+    let mut queue = SingleSlotQueue::<Rc<()>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(val.clone());
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        cons.dequeue_with(|_| panic!("processing failed"));
+    }));
+    assert!(r.is_err());
+
+    // The value was dropped, and the slot freed.
+    assert!(Rc::strong_count(&val) == 1);
+    assert!(prod.enqueue(val.clone()).is_none());
+}
+
+#[test]
+fn dequeue_replace() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.dequeue_replace(vec![0]) == Err(vec![0]));
+    prod.enqueue(vec![1]);
+    assert!(cons.dequeue_replace(vec![2]) == Ok(vec![1]));
+    assert!(!cons.is_empty());
+    assert!(prod.enqueue_overwrite(vec![3]) == Overwrite::Overwrote(vec![2]));
+    assert!(cons.dequeue() == Some(vec![3]));
+}
+
+#[test]
+fn swap() {
+    let mut queue = SingleSlotQueue::<Vec<u8>>::new();
+    let (cons, mut prod) = queue.split();
+
+    assert!(prod.swap(vec![1]) == Ok(None));
+    let mut buf = prod.swap(vec![2]).unwrap().unwrap();
+    buf.clear();
+    buf.push(3);
+    assert!(prod.swap(buf) == Ok(Some(vec![2])));
+    drop(cons);
+    assert!(prod.swap(vec![4]) == Err(vec![4]));
+}
+
+#[test]
+fn take_if() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.take_if(|_| true).is_none());
+    prod.enqueue(1);
+    assert!(cons.take_if(|&v| v > 1).is_none());
+    assert!(prod.enqueue_overwrite(2) == Overwrite::Overwrote(1));
+    assert!(cons.take_if(|&v| v > 1) == Some(2));
+    assert!(cons.is_empty());
+}
+
+#[test]
+fn take_if_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.enqueue(1);
+    let r = catch_unwind(AssertUnwindSafe(|| {
+        cons.take_if(|_| panic!("predicate failed"));
+    }));
+    assert!(r.is_err());
+
+    // The slot was given back, with the value left in it.
+    assert!(cons.take_if(|&v| v == 1) == Some(1));
+    assert!(prod.enqueue(2).is_none());
+}
+
+#[test]
+fn unchecked() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    for i in 0..10 {
+        assert!(prod.is_empty());
+        unsafe { prod.enqueue_unchecked(i) };
+        assert!(!cons.is_empty());
+        assert!(unsafe { cons.dequeue_unchecked() } == i);
+    }
+    assert!(cons.dequeue().is_none());
+}
+
+#[test]
+fn clear() {
+    let mut queue = SingleSlotQueue::<String>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(!cons.clear());
+    prod.enqueue(String::from("stale"));
+    assert!(cons.clear());
+    assert!(cons.dequeue().is_none());
+    assert!(prod.enqueue(String::from("fresh")).is_none());
+}
+
+#[test]
+fn purge() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.purge().is_none());
+    prod.enqueue(1);
+    assert!(prod.purge() == Some(1));
+    assert!(cons.dequeue().is_none());
+}
+
+#[test]
+fn is_full() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(!prod.is_full() && !cons.is_full());
+    prod.enqueue(1);
+    assert!(prod.is_full() && cons.is_full());
+    cons.dequeue();
+    assert!(!prod.is_full() && !cons.is_full());
+}
+
+#[test]
+fn into_inner() {
+    let queue = SingleSlotQueue::<String>::new();
+    assert!(queue.into_inner().is_none());
+
+    let mut queue = SingleSlotQueue::<String>::new();
+    let (cons, mut prod) = queue.split();
+    prod.enqueue(String::from("left over"));
+    drop((cons, prod));
+    assert!(queue.into_inner().as_deref() == Some("left over"));
+}
+
+#[test]
+fn get_mut() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    assert!(queue.get_mut().is_none());
+
+    let (cons, mut prod) = queue.split();
+    prod.enqueue(1);
+    drop((cons, prod));
+    *queue.get_mut().unwrap() += 1;
+
+    let (mut cons, _prod) = queue.split();
+    assert!(cons.dequeue() == Some(2));
+}
+
+#[test]
+fn new_with() {
+    let mut queue = SingleSlotQueue::new_with(String::from("default"));
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(prod.enqueue(String::from("new")).is_some());
+    assert!(cons.dequeue().as_deref() == Some("default"));
+
+    // A value left in the queue is dropped along with it.
+    let _ = SingleSlotQueue::new_with(String::from("dropped"));
+}
+
+#[test]
+fn default_and_from() {
+    #[derive(Default)]
+    struct Driver {
+        commands: SingleSlotQueue<u32>,
+    }
+
+    let mut driver = Driver::default();
+    assert!(driver.commands.get_mut().is_none());
+
+    let mut queue = SingleSlotQueue::from(5);
+    assert!(queue.get_mut() == Some(&mut 5));
+}
+
+#[test]
+fn drain() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(cons.drain().next().is_none());
+    prod.enqueue(1);
+    let mut handled = Vec::new();
+    for msg in cons.drain() {
+        handled.push(msg);
+        // Values enqueued while draining are handled too.
+        if msg < 3 {
+            prod.enqueue(msg + 1);
+        }
+    }
+    assert!(handled == [1, 2, 3]);
+}
+
+#[test]
+fn extend() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    prod.extend(0..10);
+    assert!(cons.dequeue() == Some(9));
+    prod.extend(None);
+    assert!(cons.dequeue().is_none());
+}
+
+#[test]
+fn write_grant() {
+    let mut queue = SingleSlotQueue::<[u8; 64]>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    // An abandoned grant leaves the queue empty.
+    drop(prod.write_grant().unwrap());
+    assert!(cons.is_empty());
+
+    let mut grant = prod.write_grant().unwrap();
+    unsafe {
+        grant.as_mut_ptr().cast::<u8>().write_bytes(5, 64);
+        grant.commit();
+    }
+    assert!(prod.write_grant().is_none());
+    assert!(cons.dequeue() == Some([5; 64]));
+
+    prod.write_grant().unwrap().commit_with([6; 64]);
+    assert!(cons.dequeue() == Some([6; 64]));
+}
+
+#[test]
+fn read_grant() {
+    use std::rc::Rc;
+
+    let val = Rc::new([7u8; 64]);
+    {
+        let mut queue = SingleSlotQueue::<Rc<[u8; 64]>>::new();
+        let (mut cons, mut prod) = queue.split();
+
+        assert!(cons.read_grant().is_none());
+        prod.enqueue(val.clone());
+        let grant = cons.read_grant().unwrap();
+        assert!(grant[63] == 7);
+        // The producer cannot write while the value is being read.
+        assert!(!prod.enqueue_overwrite(val.clone()).is_written());
+        grant.release();
+        assert!(cons.is_empty());
+        assert!(prod.enqueue(val.clone()).is_none());
+    }
+
+    // Every value was dropped exactly once.
+    assert!(Rc::strong_count(&val) == 1);
+}
+
+#[test]
+fn raw_pointers() {
+    let mut queue = SingleSlotQueue::<[u8; 16]>::new();
+    let (mut cons, mut prod) = queue.split();
+
+    assert!(unsafe { cons.read_ptr() }.is_none());
+    let dst = unsafe { prod.write_ptr() }.unwrap();
+    // The slot is held until the value is committed.
+    assert!(unsafe { prod.write_ptr() }.is_none());
+    assert!(unsafe { cons.read_ptr() }.is_none());
+    unsafe {
+        dst.cast::<u8>().write_bytes(9, 16);
+        prod.commit_written();
+    }
+
+    let src = unsafe { cons.read_ptr() }.unwrap();
+    let mut buf = [0; 16];
+    unsafe {
+        src.cast::<u8>()
+            .copy_to_nonoverlapping(buf.as_mut_ptr(), 16);
+        cons.mark_read();
+    }
+    assert!(buf == [9; 16]);
+    assert!(cons.is_empty());
+    assert!(prod.enqueue([1; 16]).is_none());
+}
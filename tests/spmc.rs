@@ -0,0 +1,87 @@
+use ssq::{spmc::SpmcSlot, Overwrite};
+use std::{
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Soundness test that should be run through Miri
+#[test]
+fn many_consumers() {
+    let mut slot = SpmcSlot::<u32>::new();
+    let (cons, mut prod) = slot.split();
+    let received = AtomicU32::new(0);
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            let mut cons = cons.clone();
+            let received = &received;
+            scope.spawn(move || {
+                while received.load(Ordering::Relaxed) < 100 {
+                    if cons.dequeue().is_some() {
+                        received.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
+        scope.spawn(move || {
+            for i in 0..100 {
+                while prod.enqueue(i).is_some() {}
+            }
+        });
+    });
+
+    // Each value was handed to exactly one consumer.
+    assert!(received.load(Ordering::Relaxed) == 100);
+}
+
+/// Values are rejected while a consumer is accessing the slot. Every value is accounted for
+/// exactly once: handed back when rejected or overwritten, or dequeued.
+#[test]
+fn enqueue_overwrite_rejected() {
+    let mut slot = SpmcSlot::<[u32; 1024]>::new();
+    let (cons, mut prod) = slot.split();
+    let done = AtomicBool::new(false);
+    let dequeued = AtomicU32::new(0);
+    // The handles only contend for the slot when they run in parallel.
+    let parallel = thread::available_parallelism().is_ok_and(|n| n.get() > 1);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        let consumers = [cons.clone(), cons].map(|mut cons| {
+            let (done, dequeued) = (&done, &dequeued);
+            scope.spawn(move || {
+                while !done.load(Ordering::Relaxed) {
+                    if cons.dequeue().is_some() {
+                        dequeued.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            })
+        });
+
+        let (mut sent, mut rejected, mut overwritten) = (0, 0, 0);
+        while rejected == 0 && start.elapsed() < Duration::from_secs(2) {
+            sent += 1;
+            match prod.enqueue_overwrite([sent; 1024]) {
+                Overwrite::Enqueued => {}
+                Overwrite::Overwrote(old) => {
+                    assert!(old[0] < sent);
+                    overwritten += 1;
+                }
+                Overwrite::Rejected(val) => {
+                    assert!(val[0] == sent);
+                    rejected += 1;
+                }
+            }
+        }
+        done.store(true, Ordering::Relaxed);
+        for consumer in consumers {
+            consumer.join().unwrap();
+        }
+
+        let left = u32::from(!prod.is_empty());
+        assert!(rejected + overwritten + dequeued.load(Ordering::Relaxed) + left == sent);
+        assert!(rejected > 0 || !parallel);
+    });
+}
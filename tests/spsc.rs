@@ -0,0 +1,52 @@
+use ssq::spsc::Queue;
+use std::thread;
+
+#[test]
+fn queue() {
+    let mut queue = Queue::<String, 2>::new();
+    assert!(queue.capacity() == 1 && queue.is_empty());
+    assert!(queue.enqueue(String::from("a")).is_ok());
+    assert!(queue.enqueue(String::from("b")) == Err(String::from("b")));
+    assert!(queue.len() == 1 && queue.is_full());
+    assert!(queue.peek().map(String::as_str) == Some("a"));
+
+    // A value enqueued before splitting is seen by the consumer.
+    {
+        let (producer, mut consumer) = queue.split();
+        assert!(!producer.ready() && consumer.ready());
+        assert!(consumer.peek().map(String::as_str) == Some("a"));
+        assert!(consumer.dequeue().as_deref() == Some("a"));
+    }
+
+    assert!(queue.dequeue().is_none());
+    queue.enqueue(String::from("c")).unwrap();
+    assert!(queue.dequeue().as_deref() == Some("c"));
+
+    // Dropped along with the queue.
+    queue.enqueue(String::from("d")).unwrap();
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn in_order() {
+    let mut queue = Queue::<u32, 2>::new();
+    let (mut producer, mut consumer) = queue.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 0..100 {
+                while producer.enqueue(i).is_err() {}
+            }
+        });
+
+        for i in 0..100 {
+            loop {
+                if let Some(&v) = consumer.peek() {
+                    assert!(v == i);
+                    assert!(consumer.dequeue() == Some(i));
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "stats")]
+
+use ssq::{stats::Stats, Overwrite, SingleSlotQueue};
+
+#[test]
+fn counts_traffic() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+    assert!(prod.stats() == Stats::default());
+
+    assert!(prod.enqueue(1).is_none());
+    assert!(prod.enqueue(2) == Some(2));
+    assert!(prod.enqueue_overwrite(3) == Overwrite::Overwrote(1));
+    assert!(prod.enqueue_merge(4, |a, b| a + b).is_none());
+    assert!(cons.dequeue() == Some(7));
+    assert!(cons.dequeue().is_none());
+
+    let stats = Stats {
+        enqueues: 3,
+        dequeues: 1,
+        overwrites: 1,
+        rejected: 1,
+    };
+    assert!(cons.stats() == stats);
+    assert!(prod.stats() == stats);
+}
+
+#[test]
+fn counts_disconnected_as_rejected() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+    drop(cons);
+
+    assert!(prod.enqueue(1) == Some(1));
+    assert!(prod.enqueue_overwrite(2) == Overwrite::Rejected(2));
+    assert!(prod.stats().rejected == 2);
+    assert!(prod.stats().enqueues == 0);
+}
+
+#[cfg(feature = "contention")]
+#[test]
+fn counts_contention() {
+    use ssq::stats::Contention;
+
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (mut cons, mut prod) = queue.split();
+    assert!(cons.contention() == Contention::default());
+
+    assert!(prod.enqueue(1).is_none());
+    // The producer holds the slot while merging, so the consumer finds it busy.
+    assert!(prod
+        .enqueue_merge(2, |a, b| {
+            assert!(cons.dequeue().is_none());
+            a + b
+        })
+        .is_none());
+    assert!(cons.dequeue() == Some(3));
+
+    assert!(prod.contention().busy == 1);
+    assert!(prod.contention().retries == 0);
+}
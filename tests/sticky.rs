@@ -0,0 +1,42 @@
+use ssq::{sticky::Sticky, Overwrite, SingleSlotQueue};
+use std::thread;
+
+#[test]
+fn keeps_latest() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = Sticky::new(cons);
+
+    prod.enqueue(1);
+    assert!(cons.is_updated());
+    assert!(cons.dequeue() == Some(1));
+    assert!(cons.dequeue().is_none());
+    assert!(cons.peek() == Some(1));
+
+    prod.enqueue(2);
+    assert!(cons.latest() == Some(&2));
+    assert!(!cons.is_updated());
+}
+
+#[test]
+fn latest_never_goes_back() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    let (cons, mut prod) = queue.split();
+    let mut cons = Sticky::new(cons);
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 1..=500 {
+                while prod.enqueue_overwrite(i) == Overwrite::Rejected(i) {}
+            }
+        });
+
+        let mut last = 0;
+        while last < 500 {
+            if let Some(&v) = cons.latest() {
+                assert!(v >= last);
+                last = v;
+            }
+        }
+    });
+}
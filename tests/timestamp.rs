@@ -0,0 +1,60 @@
+use ssq::{
+    timestamp::{Clock, TimestampedSlot},
+    Overwrite,
+};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Clock ticking once every time it is read.
+struct TickClock(AtomicU32);
+
+impl Clock for TickClock {
+    type Instant = u32;
+    fn now(&self) -> u32 {
+        self.0.fetch_add(1, Ordering::Relaxed)
+    }
+    fn duration_since(&self, later: u32, earlier: u32) -> u32 {
+        later.wrapping_sub(earlier)
+    }
+}
+
+#[test]
+fn stamps_enqueues() {
+    let mut slot = TimestampedSlot::<&str, _>::new(TickClock(AtomicU32::new(0)));
+    let (mut cons, mut prod) = slot.split();
+
+    assert!(prod.enqueue("a").is_none());
+    assert!(prod.enqueue("b") == Some("b"));
+    assert!(cons.peek() == Some(("a", 0)));
+    assert!(cons.dequeue() == Some(("a", 0)));
+
+    assert!(prod.enqueue_overwrite("c") == Overwrite::Enqueued);
+    assert!(cons.dequeue() == Some(("c", 2)));
+}
+
+#[test]
+fn staleness() {
+    let mut slot = TimestampedSlot::<&str, _>::new(TickClock(AtomicU32::new(0)));
+    let (mut cons, mut prod) = slot.split();
+
+    assert!(cons.age().is_none());
+
+    // Stamped at 0, and read at 1 then 2.
+    prod.enqueue("a");
+    assert!(cons.age() == Some(1));
+    assert!(cons.dequeue_fresh(1).is_none());
+    assert!(cons.is_empty());
+
+    // Stamped at 3, and read at 4.
+    prod.enqueue("b");
+    assert!(cons.dequeue_fresh(1) == Some(("b", 3)));
+}
+
+#[test]
+fn tick_counter_wraps() {
+    let mut slot = TimestampedSlot::<&str, _>::new(TickClock(AtomicU32::new(u32::MAX)));
+    let (mut cons, mut prod) = slot.split();
+
+    // Stamped at u32::MAX, and read at 0.
+    prod.enqueue("a");
+    assert!(cons.age() == Some(1));
+}
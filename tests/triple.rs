@@ -0,0 +1,25 @@
+use ssq::triple::TripleBuffer;
+use std::thread;
+
+/// Soundness test that should be run through Miri
+#[test]
+fn no_tearing() {
+    let mut buf = TripleBuffer::new([0u32; 64]);
+    let (mut cons, mut prod) = buf.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for i in 1..=1000 {
+                prod.write([i; 64]);
+            }
+        });
+
+        let mut last = 0;
+        while last < 1000 {
+            let val = cons.read();
+            assert!(val.iter().all(|v| *v == val[0]), "torn read");
+            assert!(val[0] >= last, "values went backwards");
+            last = val[0];
+        }
+    });
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "ufmt")]
+
+use ssq::{Closed, DequeueError, Disconnected, EnqueueError, LossyEnqueue, SingleSlotQueue};
+use ufmt::uwrite;
+
+struct Buf(String);
+
+impl ufmt::uWrite for Buf {
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+fn format(f: impl FnOnce(&mut Buf)) -> String {
+    let mut buf = Buf(String::new());
+    f(&mut buf);
+    buf.0
+}
+
+#[test]
+fn debug() {
+    let mut queue = SingleSlotQueue::<u32>::new();
+    assert!(
+        format(|b| uwrite!(b, "{:?}", queue).unwrap()) == r#"SingleSlotQueue { state: Empty }"#
+    );
+
+    let (cons, mut prod) = queue.split();
+    prod.enqueue(1);
+    assert!(
+        format(|b| uwrite!(b, "{:?}", cons).unwrap())
+            == r#"Consumer { state: Full, closed: false }"#
+    );
+    drop(cons);
+    assert!(
+        format(|b| uwrite!(b, "{:?}", prod).unwrap())
+            == r#"Producer { state: Full, consumer_connected: false }"#
+    );
+    assert!(format(|b| uwrite!(b, "{:?}", LossyEnqueue::Overwrote).unwrap()) == "Overwrote");
+}
+
+#[test]
+fn display() {
+    assert!(format(|b| uwrite!(b, "{}", Closed).unwrap()) == "queue closed");
+    assert!(format(|b| uwrite!(b, "{}", Disconnected(1)).unwrap()) == "consumer disconnected");
+    assert!(format(|b| uwrite!(b, "{}", EnqueueError::Full(1)).unwrap()) == "queue full");
+    assert!(format(|b| uwrite!(b, "{}", DequeueError::Empty).unwrap()) == "queue empty");
+}
@@ -0,0 +1,86 @@
+use ssq::watch::Watch;
+use std::thread;
+
+#[test]
+fn consumers_see_latest() {
+    let mut watch = Watch::<u32, 3>::new();
+    let (cons, mut prod) = watch.split();
+
+    thread::scope(|scope| {
+        for mut cons in cons {
+            scope.spawn(move || {
+                let mut last = 0;
+                while last < 100 {
+                    if let Some(v) = cons.get_changed() {
+                        assert!(v >= last, "values went backwards");
+                        last = v;
+                    }
+                }
+            });
+        }
+
+        for i in 1..=100 {
+            // Readers never hold up the producer.
+            assert!(prod.enqueue(i) == if i == 1 { None } else { Some(i - 1) });
+        }
+    });
+}
+
+/// Soundness test that should be run through Miri
+#[test]
+fn torn_reads_are_retried() {
+    let mut watch = Watch::<[u64; 8], 2>::new();
+    let (cons, mut prod) = watch.split();
+
+    thread::scope(|scope| {
+        for mut cons in cons {
+            scope.spawn(move || {
+                let mut last = 0;
+                while last < 1000 {
+                    if let Some(v) = cons.get() {
+                        assert!(v.iter().all(|&x| x == v[0]), "torn read");
+                        last = v[0];
+                    }
+                }
+            });
+        }
+
+        for i in 1..=1000 {
+            prod.enqueue([i; 8]);
+        }
+    });
+}
+
+#[test]
+fn drops_value() {
+    use std::rc::Rc;
+
+    let val = Rc::new(());
+    {
+        let mut watch = Watch::<Rc<()>, 1>::new();
+        let (_, mut prod) = watch.split();
+        assert!(prod.enqueue(val.clone()).is_none());
+        assert!(prod.enqueue(val.clone()).is_some());
+    }
+
+    // Nothing was leaked.
+    assert!(Rc::strong_count(&val) == 1);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn changed() {
+    let mut watch = Watch::<u32, 1>::new();
+    let ([mut cons], mut prod) = watch.split();
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            prod.enqueue(1);
+        });
+        futures::executor::block_on(async {
+            assert_eq!(cons.changed().await, Ok(()));
+            assert_eq!(cons.get(), Some(1));
+            assert_eq!(cons.changed().await, Err(ssq::Closed));
+        });
+    });
+}